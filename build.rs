@@ -0,0 +1,32 @@
+use std::{env, fs, path::Path};
+
+// Generates one example test per inputs/dayN directory holding a non empty test_input.txt, see tests/examples.rs
+fn main() {
+    println!("cargo:rerun-if-changed=inputs");
+
+    let mut days: Vec<(u32, String)> = fs::read_dir("inputs")
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                // skip examples nobody pasted in yet, i.e. right after `new dayN`
+                .filter(|e| {
+                    fs::metadata(e.path().join("test_input.txt")).is_ok_and(|m| m.len() > 0)
+                })
+                .filter_map(|e| e.file_name().into_string().ok())
+                .filter_map(|day| Some((day.strip_prefix("day")?.parse::<u32>().ok()?, day)))
+                .collect()
+        })
+        .unwrap_or_default();
+    days.sort();
+
+    let tests: String = days
+        .iter()
+        .map(|(_, day)| {
+            format!("#[test]\nfn {day}_examples() {{\n    check_examples(\"{day}\");\n}}\n\n")
+        })
+        .collect();
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("example_tests.rs"), tests)
+        .expect("failed to write example_tests.rs");
+}
@@ -0,0 +1,50 @@
+use core::fmt;
+
+/// Value produced by solving a single puzzle part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    // rendered output spanning several lines, i.e. day10's CRT screen
+    Multiline(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(i) => write!(f, "{}", i),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Multiline(s) => write!(f, "{}", s.trim_end()),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(i: i32) -> Self {
+        Answer::Int(i.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(i: u32) -> Self {
+        Answer::Int(i.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(i: i64) -> Self {
+        Answer::Int(i)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(i: usize) -> Self {
+        Answer::Int(i as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
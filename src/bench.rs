@@ -0,0 +1,100 @@
+use std::time::{Duration, Instant};
+
+use crate::{configured_solution, AocError, Part};
+
+/// Spread of the timings collected over all benchmark runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    fn from_samples(mut samples: Vec<Duration>) -> Timings {
+        samples.sort();
+        Timings {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BenchReport {
+    pub day: String,
+    pub runs: usize,
+    pub parse: Timings,
+    pub parts: Vec<(Part, Timings)>,
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+    (res, start.elapsed())
+}
+
+/// Parses `input` and solves each part `runs` times, timing parsing and solving separately.
+pub fn bench(
+    aoc_day: &str,
+    input: &str,
+    runs: usize,
+    options: &[(String, String)],
+) -> Result<BenchReport, AocError> {
+    if runs == 0 {
+        return Err("benchmark needs at least 1 run".into());
+    }
+    let solution = configured_solution(aoc_day, options)?;
+
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut parsed = None;
+    for _ in 0..runs {
+        let (p, elapsed) = time(|| solution.parse(input));
+        parse_samples.push(elapsed);
+        parsed = Some(p?);
+    }
+    let parsed = parsed.ok_or("no parsed input after benchmark runs")?;
+
+    let parts = Part::BOTH
+        .iter()
+        .map(|&part| {
+            let samples = (0..runs)
+                .map(|_| {
+                    let (answer, elapsed) = time(|| solution.solve(&*parsed, part));
+                    answer.map(|_| elapsed)
+                })
+                .collect::<Result<Vec<Duration>, AocError>>()?;
+            Ok((part, Timings::from_samples(samples)))
+        })
+        .collect::<Result<Vec<(Part, Timings)>, AocError>>()?;
+
+    Ok(BenchReport {
+        day: aoc_day.to_string(),
+        runs,
+        parse: Timings::from_samples(parse_samples),
+        parts,
+    })
+}
+
+#[cfg(test)]
+mod bench_test {
+    use super::*;
+
+    #[test]
+    fn bench_test() {
+        assert!(bench("day1", "1000", 0, &[]).is_err());
+        assert!(matches!(
+            bench("day1", "1000\nabc", 3, &[]),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+
+        let report = bench("day1", "1000\n2000\n\n3000", 5, &[]).unwrap();
+        assert_eq!(report.runs, 5);
+        let parts: Vec<Part> = report.parts.iter().map(|&(part, _)| part).collect();
+        assert_eq!(parts, Part::BOTH);
+        for t in report.parts.iter().map(|(_, t)| t).chain([&report.parse]) {
+            assert!(t.min <= t.median && t.median <= t.max, "{:?}", t);
+        }
+    }
+}
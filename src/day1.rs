@@ -1,68 +1,366 @@
-use std::error::Error;
+use core::fmt;
+use itertools::Itertools;
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
+
+use crate::{Answer, AocError, Part, Solution};
 
 // DAY 1
 struct Elf {
-    calories: i32,
+    calories: i64,
 }
 
-struct ElfExpedition {
+pub struct ElfExpedition {
     elves: Vec<Elf>,
-    elf_candidate: Vec<i32>,
 }
 
 impl ElfExpedition {
-    pub fn new() -> ElfExpedition {
-        return ElfExpedition {
-            elves: vec![],
-            elf_candidate: vec![],
+    /// Sum of the `n` highest calorie totals, selecting them in linear time instead of sorting every elf.
+    fn top_total(&self, n: usize) -> Result<i64, AocError> {
+        let mut calories = self.elves.iter().map(|e| e.calories).collect_vec();
+        if n < calories.len() {
+            calories.select_nth_unstable_by(n, |a, b| b.cmp(a));
+            calories.truncate(n);
+        }
+        checked_total(calories)
+    }
+
+    fn stats(&self, buckets: usize) -> Option<CalorieStats> {
+        let mut calories = self.elves.iter().map(|e| e.calories).collect_vec();
+        let count = calories.len();
+        let (&min, &max) = (calories.iter().min()?, calories.iter().max()?);
+        let mean = calories.iter().map(|&c| c as f64).sum::<f64>() / count as f64;
+        let (_, &mut upper, _) = calories.select_nth_unstable(count / 2);
+        let median = if count % 2 == 1 {
+            upper as f64
+        } else {
+            // the lower middle is the largest of the values left of the upper one
+            let lower = *calories[..count / 2].iter().max()?;
+            (lower as f64 + upper as f64) / 2.0
         };
+
+        // equal width buckets covering min..=max, rounding the width up can leave buckets past max
+        // totals span up to the whole i64 range, so widths and bounds are worked out in 128 bits
+        let buckets = buckets.clamp(1, count);
+        let width = (max.abs_diff(min) as u128 + 1).div_ceil(buckets as u128);
+        let mut histogram = vec![0; buckets];
+        for &c in &calories {
+            histogram[(c.abs_diff(min) as u128 / width) as usize] += 1;
+        }
+        let histogram = histogram
+            .into_iter()
+            .enumerate()
+            .map(|(i, elves)| (min as i128 + (i as u128 * width) as i128, elves))
+            .filter(|&(from, _)| from <= max as i128)
+            .map(|(from, elves)| {
+                let to = (from + width as i128 - 1).min(max as i128);
+                (from as i64, to as i64, elves)
+            })
+            .collect();
+
+        Some(CalorieStats {
+            count,
+            min,
+            max,
+            mean,
+            median,
+            histogram,
+        })
+    }
+}
+
+struct CalorieStats {
+    count: usize,
+    min: i64,
+    max: i64,
+    mean: f64,
+    median: f64,
+    /// Number of elves carrying a total within each inclusive (from, to) range.
+    histogram: Vec<(i64, i64, usize)>,
+}
+
+impl fmt::Display for CalorieStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves: {}", self.count)?;
+        writeln!(f, "min: {}", self.min)?;
+        writeln!(f, "max: {}", self.max)?;
+        writeln!(f, "mean: {:.1}", self.mean)?;
+        writeln!(f, "median: {:.1}", self.median)?;
+        writeln!(f, "histogram:")?;
+        let most = self
+            .histogram
+            .iter()
+            .map(|&(_, _, elves)| elves)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for &(from, to, elves) in &self.histogram {
+            // bars are scaled so the fullest bucket is 40 wide
+            let bar = "#".repeat((elves * 40).div_ceil(most));
+            writeln!(f, "{:>7}-{:<7} {} {}", from, to, bar, elves)?;
+        }
+        Ok(())
     }
+}
+
+/// The `n` highest totals seen so far, kept in a min-heap so the lowest of them is the one replaced.
+struct TopTotals {
+    n: usize,
+    heap: BinaryHeap<Reverse<i64>>,
+}
 
-    fn pack_snack(&mut self, s: i32) {
-        self.elf_candidate.push(s);
+impl TopTotals {
+    fn new(n: usize) -> TopTotals {
+        TopTotals {
+            n,
+            // n comes from the command line, the heap only grows as far as there are elves
+            heap: BinaryHeap::with_capacity(n.min(1024)),
+        }
     }
 
-    fn pack_elf(&mut self) {
-        if self.elf_candidate.is_empty() {
-            return;
+    fn push(&mut self, total: i64) {
+        if self.heap.len() < self.n {
+            self.heap.push(Reverse(total));
+        } else if self
+            .heap
+            .peek()
+            .is_some_and(|&Reverse(lowest)| lowest < total)
+        {
+            self.heap.pop();
+            self.heap.push(Reverse(total));
         }
-        let calorie_total = self.elf_candidate.iter().sum();
-        self.elves.push(Elf {
-            calories: calorie_total,
-        });
-        self.elf_candidate.clear();
     }
 
-    fn top_three_total(&mut self) -> i32 {
-        self.elves.sort_by(|a, b| b.calories.cmp(&a.calories));
-        self.elves.iter().take(3).map(|e| e.calories).sum()
+    fn best(&self) -> i64 {
+        self.heap.iter().map(|&Reverse(t)| t).max().unwrap_or(0)
+    }
+
+    fn sum(&self) -> Result<i64, AocError> {
+        checked_total(self.heap.iter().map(|&Reverse(t)| t))
     }
 }
 
-fn parse_elves(lines: Vec<String>) -> Result<ElfExpedition, Box<dyn Error>> {
-    let mut elves = lines
-        .iter()
-        .fold(ElfExpedition::new(), |mut acc, l| match l {
-            l if l.len() == 0 => {
-                acc.pack_elf();
-                acc
+/// Calls `on_elf` with every elf's calorie total, reading one line at a time so only the current total is kept.
+fn read_elves(mut input: impl BufRead, mut on_elf: impl FnMut(i64)) -> Result<(), AocError> {
+    let mut line = String::new();
+    let mut line_no = 0;
+    let mut total: Option<i64> = None;
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_no += 1;
+        match line.trim_end_matches(['\n', '\r']) {
+            "" => {
+                if let Some(t) = total.take() {
+                    on_elf(t);
+                }
             }
             l => {
-                let cals = l.parse::<i32>().unwrap();
-                acc.pack_snack(cals);
-                acc
+                let cals = l.parse::<i64>().map_err(|e| {
+                    AocError::parse(1, l, format!("invalid snack calories, {}", e)).on_line(line_no)
+                })?;
+                let t = total.unwrap_or(0).checked_add(cals).ok_or_else(|| {
+                    AocError::parse(1, l, "calorie total overflows").on_line(line_no)
+                })?;
+                total = Some(t);
             }
-        });
+        }
+    }
     //collect the last elf
-    elves.pack_elf();
-    Ok(elves)
+    if let Some(t) = total {
+        on_elf(t);
+    }
+    Ok(())
 }
 
-pub fn result(lines: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let mut elves = parse_elves(lines)?;
+// sum of calorie totals, i.e. of the top elves, which may not fit an i64 even though every total does
+fn checked_total(totals: impl IntoIterator<Item = i64>) -> Result<i64, AocError> {
+    totals
+        .into_iter()
+        .try_fold(0i64, |sum, t| sum.checked_add(t))
+        .ok_or_else(|| "sum of the top calorie totals overflows".into())
+}
 
-    println!("result is: {}", elves.top_three_total());
-    Ok(())
+fn parse_elves(input: impl BufRead) -> Result<ElfExpedition, AocError> {
+    let mut elves = vec![];
+    read_elves(input, |calories| elves.push(Elf { calories }))?;
+    Ok(ElfExpedition { elves })
+}
+
+pub struct Day1 {
+    top: usize,
+    report: Report,
+    buckets: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum Report {
+    Top,
+    Stats,
+}
+
+impl Default for Day1 {
+    fn default() -> Self {
+        Day1 {
+            top: 3,
+            report: Report::Top,
+            buckets: 10,
+        }
+    }
+}
+
+impl Solution for Day1 {
+    type Input = ElfExpedition;
+
+    fn parse(&self, input: &str) -> Result<ElfExpedition, AocError> {
+        parse_elves(input.as_bytes())
+    }
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), AocError> {
+        let number = |value: &str| {
+            value
+                .parse()
+                .map_err(|e| AocError::invalid_option(option, format!("{}", e)))
+        };
+        match (option, value) {
+            ("top", n) => self.top = number(n)?,
+            ("buckets", n) => self.buckets = number(n)?,
+            ("report", "top") => self.report = Report::Top,
+            ("report", "stats") => self.report = Report::Stats,
+            ("report", _) => return Err(AocError::invalid_option(option, "expected top or stats")),
+            _ => {
+                return Err(AocError::invalid_option(
+                    option,
+                    "unknown option, day1 supports [top, report, buckets]",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn solve_streaming(&self, input: &mut dyn BufRead) -> Result<Vec<(Part, Answer)>, AocError> {
+        if self.report == Report::Stats {
+            return Err("the stats report needs every elf's total, it can't be streamed".into());
+        }
+        // part 1 is the best total, which is also the best of the top ones
+        let mut top = TopTotals::new(self.top.max(1));
+        read_elves(input, |total| top.push(total))?;
+        let top_total = if self.top == 0 { 0 } else { top.sum()? };
+        Ok(vec![
+            (Part::One, top.best().into()),
+            (Part::Two, top_total.into()),
+        ])
+    }
+
+    fn part1(&self, elves: &ElfExpedition) -> Result<Answer, AocError> {
+        Ok(elves.top_total(1)?.into())
+    }
+
+    fn part2(&self, elves: &ElfExpedition) -> Result<Answer, AocError> {
+        match self.report {
+            Report::Top => Ok(elves.top_total(self.top)?.into()),
+            Report::Stats => {
+                let stats = elves.stats(self.buckets).ok_or("no elves to report on")?;
+                Ok(Answer::Multiline(stats.to_string()))
+            }
+        }
+    }
 }
 
 // DAY 1 END
+
+#[cfg(test)]
+mod day1_test {
+    use std::io;
+
+    use super::*;
+
+    const ELVES: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    #[test]
+    fn top_total_test() {
+        let elves = parse_elves(ELVES.as_bytes()).unwrap();
+        assert_eq!(elves.top_total(1).unwrap(), 24000);
+        assert_eq!(elves.top_total(3).unwrap(), 45000);
+        assert_eq!(elves.top_total(10).unwrap(), 55000);
+        assert_eq!(elves.top_total(0).unwrap(), 0);
+        let huge = format!("{}\n\n{}", i64::MAX, i64::MAX);
+        let elves = parse_elves(huge.as_bytes()).unwrap();
+        assert_eq!(elves.top_total(1).unwrap(), i64::MAX);
+        assert!(elves.top_total(2).is_err());
+    }
+
+    #[test]
+    fn stats_test() {
+        let stats = parse_elves(ELVES.as_bytes()).unwrap().stats(2).unwrap();
+        assert_eq!((stats.count, stats.min, stats.max), (5, 4000, 24000));
+        assert_eq!((stats.mean, stats.median), (11000.0, 10000.0));
+        assert_eq!(stats.histogram, vec![(4000, 14000, 4), (14001, 24000, 1)]);
+        let stats = parse_elves(ELVES.as_bytes()).unwrap().stats(4).unwrap();
+        assert_eq!(
+            stats.histogram,
+            vec![
+                (4000, 9000, 2),
+                (9001, 14001, 2),
+                (14002, 19002, 0),
+                (19003, 24000, 1)
+            ]
+        );
+        // a width of 2 only needs 3 of the 4 buckets to cover 1..=5
+        let stats = parse_elves("1\n\n2\n\n3\n\n4\n\n5".as_bytes())
+            .unwrap()
+            .stats(4)
+            .unwrap();
+        assert_eq!(stats.histogram, vec![(1, 2, 2), (3, 4, 2), (5, 5, 1)]);
+        assert!(parse_elves("".as_bytes()).unwrap().stats(2).is_none());
+        // the widest possible range still gets 2 equal buckets
+        let extremes = format!("{}\n\n{}", i64::MIN, i64::MAX);
+        let stats = parse_elves(extremes.as_bytes()).unwrap().stats(2).unwrap();
+        assert_eq!(stats.histogram, vec![(i64::MIN, -1, 1), (0, i64::MAX, 1)]);
+    }
+    #[test]
+    fn streaming_test() {
+        let mut day = Day1::default();
+        assert_eq!(
+            day.solve_streaming(&mut "1\n2\r\n\n\n4\n\n2\n".as_bytes())
+                .unwrap(),
+            vec![(Part::One, Answer::Int(4)), (Part::Two, Answer::Int(9))]
+        );
+        assert_eq!(
+            day.solve_streaming(&mut "1\n\n2x\n".as_bytes()).unwrap_err().to_string(),
+            "parse error at line 3, column 1: invalid snack calories, invalid digit found in string, got: '2x'"
+        );
+
+        // a huge top just keeps every elf
+        day.configure("top", &usize::MAX.to_string()).unwrap();
+        assert_eq!(
+            day.solve_streaming(&mut "1\n\n2\n".as_bytes()).unwrap(),
+            vec![(Part::One, Answer::Int(2)), (Part::Two, Answer::Int(3))]
+        );
+        day.configure("top", "3").unwrap();
+
+        // both ways of reading the input report an overflowing top total the same way
+        let huge = format!("{}\n\n{}", i64::MAX, i64::MAX);
+        assert_eq!(
+            day.solve_streaming(&mut huge.as_bytes())
+                .unwrap_err()
+                .to_string(),
+            day.part2(&day.parse(&huge).unwrap())
+                .unwrap_err()
+                .to_string()
+        );
+
+        // generated inventory of 100k elves, read through a small buffer
+        let generated = (0..100_000)
+            .map(|i| format!("{}\n{}\n\n", i % 1000, i))
+            .collect::<String>();
+        let mut reader = io::BufReader::with_capacity(64, generated.as_bytes());
+        let answers = day.solve_streaming(&mut reader).unwrap();
+        assert_eq!(answers[0], (Part::One, Answer::Int(999 + 99_999)));
+        assert_eq!(
+            answers[1],
+            (Part::Two, Answer::Int(99_999 * 3 - 3 + 999 + 998 + 997))
+        );
+    }
+}
@@ -1,39 +1,44 @@
-use std::error::Error;
+use crate::{error::parse_lines, Answer, AocError, Solution};
 
-enum Inst {
+pub enum Inst {
     AddX(i32),
     NoOp,
 }
 
 impl Inst {
-    fn new(l: &String) -> Result<Inst, String> {
-        if l.as_str() == "noop" {
+    fn new(l: &str) -> Result<Inst, AocError> {
+        if l == "noop" {
             Ok(Inst::NoOp)
         } else {
-            let parts: Vec<&str> = l.split(" ").collect();
+            let parts: Vec<&str> = l.split(' ').collect();
             if parts.len() != 2 || parts[0] != "addx" {
-                Err(format!(
-                    "invalid input line - expected 'addx N', got: {}",
-                    l
+                Err(AocError::parse(
+                    1,
+                    l,
+                    "invalid input line - expected 'noop' or 'addx N'",
                 ))
             } else {
-                let n = parts[1]
-                    .parse::<i32>()
-                    .map_err(|e| format!("Invalid Input - can't parse addx arg: {}", e))?;
+                let n = parts[1].parse::<i32>().map_err(|e| {
+                    AocError::parse_token(
+                        l,
+                        parts[1],
+                        format!("Invalid Input - can't parse addx arg: {}", e),
+                    )
+                })?;
                 Ok(Inst::AddX(n))
             }
         }
     }
 }
 
-struct CPU {
+struct Cpu {
     x: i32,
     current_cycle: u32,
     signal_recording: Vec<(u32, i32)>,
     crt_output: Vec<char>,
 }
 
-impl CPU {
+impl Cpu {
     fn run(&mut self, i: &Inst) {
         self.current_cycle += 1;
         self.check_signal();
@@ -62,7 +67,7 @@ impl CPU {
     fn check_signal(&mut self) {
         // println!("check_signal: {}", self.CurrentCycle);
         if self.current_cycle == 20
-            || (self.current_cycle > 20 && (self.current_cycle - 20) % 40 == 0)
+            || (self.current_cycle > 20 && (self.current_cycle - 20).is_multiple_of(40))
         {
             // println!("Interesting Signal, Cycle: {}, X:{}", self.CurrentCycle, self.X);
             self.signal_recording.push((self.current_cycle, self.x));
@@ -70,7 +75,7 @@ impl CPU {
 
         self.crt_output.push(self.draw_sprite());
 
-        if self.current_cycle % 40 == 0 {
+        if self.current_cycle.is_multiple_of(40) {
             self.crt_output.push('\n')
         }
     }
@@ -82,8 +87,8 @@ impl CPU {
             .collect()
     }
 
-    fn new() -> CPU {
-        CPU {
+    fn new() -> Cpu {
+        Cpu {
             x: 1,
             current_cycle: 0,
             signal_recording: vec![],
@@ -92,19 +97,34 @@ impl CPU {
     }
 }
 
-pub(crate) fn result(ls: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let program: Result<Vec<Inst>, String> = ls.iter().map(Inst::new).collect();
-    let program = program?;
-    let mut cpu = CPU::new();
+impl Cpu {
+    fn execute(program: &[Inst]) -> Cpu {
+        let mut cpu = Cpu::new();
+        program.iter().for_each(|i| {
+            cpu.run(i);
+        });
+        cpu
+    }
+}
 
-    program.iter().for_each(|i| {
-        cpu.run(i);
-    });
+pub struct Day10;
 
-    let signal_strengths: i32 = cpu.signal_strengths().iter().sum();
-    println!("Par1 Result: {}", signal_strengths);
+impl Solution for Day10 {
+    type Input = Vec<Inst>;
+
+    fn parse(&self, ls: &str) -> Result<Vec<Inst>, AocError> {
+        parse_lines(ls, Inst::new)
+    }
 
-    let crt_out: String = cpu.crt_output.into_iter().collect();
-    println!("{}", crt_out);
-    Ok(())
+    fn part1(&self, program: &Vec<Inst>) -> Result<Answer, AocError> {
+        let cpu = Cpu::execute(program);
+        let signal_strengths: i32 = cpu.signal_strengths().iter().sum();
+        Ok(signal_strengths.into())
+    }
+
+    fn part2(&self, program: &Vec<Inst>) -> Result<Answer, AocError> {
+        let cpu = Cpu::execute(program);
+        let crt_out: String = cpu.crt_output.into_iter().collect();
+        Ok(Answer::Multiline(crt_out))
+    }
 }
@@ -1,4 +1,4 @@
-pub use monkeygame::result;
+pub use monkeygame::Day11;
 
 mod monkeygame;
 mod parser;
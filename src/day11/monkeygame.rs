@@ -1,9 +1,9 @@
 use core::fmt;
-use std::error::Error;
 
 use itertools::Itertools;
 
 use crate::day11::parser;
+use crate::{Answer, AocError, Solution};
 
 #[derive(Debug, Clone)]
 pub(crate) struct MonkeyTest {
@@ -21,7 +21,7 @@ impl MonkeyTest {
     }
 
     fn apply(&self, new_worry_level: u64) -> usize {
-        if new_worry_level % self.param == 0 {
+        if new_worry_level.is_multiple_of(self.param) {
             self.truthy_target
         } else {
             self.falsy_target
@@ -58,14 +58,14 @@ impl MonkeyOp {
 
     fn arg0(&self, arg1: u64) -> u64 {
         match self.arg {
-            MonkeyOpArg::Old => arg1.clone(),
+            MonkeyOpArg::Old => arg1,
             MonkeyOpArg::IntArg(arg0) => arg0,
         }
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct Monkey {
+#[derive(Debug, Clone)]
+pub struct Monkey {
     id: usize,
     items: Vec<u64>,
     operation: MonkeyOp,
@@ -87,26 +87,27 @@ impl Monkey {
     pub fn new(id: usize, items: Vec<u64>, op: MonkeyOp, test: MonkeyTest) -> Monkey {
         Monkey {
             id,
-            items: items,
+            items,
             operation: op,
-            test: test,
+            test,
             inspected_items: 0,
         }
     }
 
-    fn round(&self, div: &u64) -> (usize, Vec<(usize, u64)>) {
+    fn round(&self, relief: &Relief) -> (usize, Vec<(usize, u64)>) {
         let thrown_items: Vec<(usize, u64)> = self
             .items
             .iter()
             .map(|&i| {
                 let new_worry_level = self.operation.apply(i);
-                //let new_worry_level = new_worry_level / 3; // This operation rounds towards zero,  so I think we're fine?
-
-                // use Chinese Reminder Theorem https://brilliant.org/wiki/chinese-remainder-theorem/
-                // since all divisors are prime, we can find their GCD by multiplying them and then use this GCD to modulo the worry level
-                //  without affecting divisability test
-                // thanks Reddit https://www.reddit.com/r/adventofcode/comments/zifqmh/comment/j26b81u/?utm_source=share&utm_medium=web2x&context=3
-                let new_worry_level = new_worry_level % div;
+                let new_worry_level = match relief {
+                    Relief::Bored => new_worry_level / 3, // This operation rounds towards zero,  so I think we're fine?
+                    // use Chinese Reminder Theorem https://brilliant.org/wiki/chinese-remainder-theorem/
+                    // since all divisors are prime, we can find their GCD by multiplying them and then use this GCD to modulo the worry level
+                    //  without affecting divisability test
+                    // thanks Reddit https://www.reddit.com/r/adventofcode/comments/zifqmh/comment/j26b81u/?utm_source=share&utm_medium=web2x&context=3
+                    Relief::Modulo(div) => new_worry_level % div,
+                };
                 let target_monkey = self.test.apply(new_worry_level);
                 (target_monkey, new_worry_level)
             })
@@ -120,64 +121,86 @@ impl Monkey {
     }
 }
 
+/// How worry levels are kept in check after each inspection.
+enum Relief {
+    Bored,
+    Modulo(u64),
+}
+
 struct MonkeyGame {
     monkeys: Vec<Monkey>,
-    gcd: u64,
+    relief: Relief,
 }
 
-impl MonkeyGame {
-    fn print(&self) {
+impl fmt::Display for MonkeyGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let ms = self
             .monkeys
             .iter()
             .map(|m| format!("{}", m))
             .collect::<Vec<String>>();
-        println!("Monkeys:\n{}\n", ms.join(""));
+        writeln!(f, "Monkeys:\n{}", ms.join(""))
     }
+}
 
-    fn new(monkeys: Vec<Monkey>) -> MonkeyGame {
-        let gcd = monkeys.iter().map(|m| m.test.param).product();
-        MonkeyGame { monkeys, gcd }
+impl MonkeyGame {
+    fn new(monkeys: Vec<Monkey>, bored_monkeys: bool) -> MonkeyGame {
+        let relief = if bored_monkeys {
+            Relief::Bored
+        } else {
+            Relief::Modulo(monkeys.iter().map(|m| m.test.param).product())
+        };
+        MonkeyGame { monkeys, relief }
     }
 
     fn round(&mut self) {
         for i in 0..self.monkeys.len() {
-            // self.print();
-            let (inspected_items, thrown_items) = self.monkeys[i].round(&self.gcd);
+            // println!("{}", self);
+            let (inspected_items, thrown_items) = self.monkeys[i].round(&self.relief);
             //  println!("inspected_items: {}, thrown_items: {:?}", inspected_items, thrown_items);
             for (target_monkey_id, item) in thrown_items {
-                let target_monkey = self
-                    .monkeys
-                    .get_mut(target_monkey_id)
-                    .expect("Item thrown to unknown Monkey.");
-                target_monkey.catch_item(item);
+                // targets are checked against the number of monkeys when parsing
+                self.monkeys[target_monkey_id].catch_item(item);
             }
             let update_m = self.monkeys.get_mut(i).unwrap();
             update_m.inspected_items += inspected_items;
             update_m.items.clear();
         }
     }
+
+    fn monkey_business(&self) -> usize {
+        self.monkeys
+            .iter()
+            .map(|m| m.inspected_items)
+            .sorted()
+            .rev()
+            .take(2)
+            .product()
+    }
 }
 
-pub fn result(input: String) -> Result<(), Box<dyn Error>> {
-    let monkeys = parser::parse_input(&input)?;
-    let mut mg = MonkeyGame::new(monkeys);
-    for i in 0..10000 {
-        println!("Round: {}", i);
-        mg.round();
-    }
-    mg.print();
-    let monkey_business: Vec<usize> = mg
-        .monkeys
-        .iter()
-        .map(|m| m.inspected_items)
-        .sorted()
-        .rev()
-        .take(2)
-        .collect();
-    println!(
-        "Part 1 Result: {:?}",
-        monkey_business.iter().product::<usize>()
-    );
-    Ok(())
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Monkey>, AocError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(&self, monkeys: &Vec<Monkey>) -> Result<Answer, AocError> {
+        let mut mg = MonkeyGame::new(monkeys.clone(), true);
+        for _ in 0..20 {
+            mg.round();
+        }
+        Ok(mg.monkey_business().into())
+    }
+
+    fn part2(&self, monkeys: &Vec<Monkey>) -> Result<Answer, AocError> {
+        let mut mg = MonkeyGame::new(monkeys.clone(), false);
+        for _ in 0..10000 {
+            mg.round();
+        }
+        Ok(mg.monkey_business().into())
+    }
 }
@@ -1,98 +1,145 @@
 use crate::day11::monkeygame::{Monkey, MonkeyOp, MonkeyOpArg, MonkeyTest};
+use crate::AocError;
 use pest::iterators::Pair;
 use pest::Parser;
+use std::str::FromStr;
 
 #[derive(Parser)]
 #[grammar = "day11/day11.pest"]
 struct MonkeySpecParser;
 
-pub(crate) fn parse_input(input: &str) -> Result<Vec<Monkey>, String> {
-    let parsed = MonkeySpecParser::parse(Rule::monkeySpecs, input).map_err(|e| format!("{}", e))?;
+pub(crate) fn parse_input(input: &str) -> Result<Vec<Monkey>, AocError> {
+    let parsed: Vec<Pair<Rule>> = MonkeySpecParser::parse(Rule::monkeySpecs, input)?.collect();
+    let monkey_count = parsed.len();
 
-    let monkeys: Result<Vec<Monkey>, String> = parsed.map(parse_monkey).collect();
+    parsed
+        .into_iter()
+        .map(|p| parse_monkey(p, monkey_count))
+        .collect()
+}
+
+// parse error pointing at the start of p within the whole input
+fn error_at(p: &Pair<Rule>, message: impl Into<String>) -> AocError {
+    let (line, column) = p.as_span().start_pos().line_col();
+    let text = p.as_str().lines().next().unwrap_or_default();
+    AocError::parse(column, text, message).on_line(line)
+}
 
-    monkeys.map_err(|e| format!("{}", e))
+fn number<T: FromStr>(p: &Pair<Rule>) -> Result<T, AocError>
+where
+    T::Err: std::fmt::Display,
+{
+    p.as_str()
+        .parse::<T>()
+        .map_err(|e| error_at(p, format!("invalid number, {}", e)))
 }
 
-fn parse_monkey(p: Pair<Rule>) -> Result<Monkey, String> {
-    let mut pairs = p.into_inner();
-    let monkey_spec = pairs.next().ok_or("Invalid input expected monkeyId")?;
+// monkeys throw items to each other by their position in the input, so targets must be below monkey_count
+fn parse_monkey(p: Pair<Rule>, monkey_count: usize) -> Result<Monkey, AocError> {
+    let missing = |spec: &str| error_at(&p, format!("Invalid input expected {}", spec));
+    let mut pairs = p.clone().into_inner();
+    let monkey_spec = pairs.next().ok_or_else(|| missing("monkeyId"))?;
     let m_id = match monkey_spec.as_rule() {
-        Rule::monkeyId => monkey_spec
-            .as_str()
-            .parse::<usize>()
-            .map_err(|e| format!("{}", e)),
-        _ => Err("Invalid input, expected ASCII_DIGIT+ representing monkeyId".into()),
+        Rule::monkeyId => number::<usize>(&monkey_spec),
+        _ => Err(error_at(
+            &monkey_spec,
+            "Invalid input, expected ASCII_DIGIT+ representing monkeyId",
+        )),
     };
-    let starting_items_spec = pairs
-        .next()
-        .ok_or("Invalid input expected startingItemsSpec")?;
+    let starting_items_spec = pairs.next().ok_or_else(|| missing("startingItemsSpec"))?;
     let starting_items = match starting_items_spec.as_rule() {
         Rule::startingItemsSpec => {
-            let starting_items: Result<Vec<u64>, String> = starting_items_spec
+            let starting_items: Result<Vec<u64>, AocError> = starting_items_spec
                 .into_inner()
-                .map(|i| i.as_str().parse::<u64>().map_err(|e| format!("{}", e)))
+                .map(|i| number::<u64>(&i))
                 .collect();
             let starting_items = starting_items?;
             Ok(starting_items)
         }
-        _ => Err(format!(
-            "Invalid input, expected startingItemsSpec, got: {}",
-            starting_items_spec.as_str()
+        _ => Err(error_at(
+            &starting_items_spec,
+            "Invalid input, expected startingItemsSpec",
         )),
     };
 
-    let operation_spec = pairs
-        .next()
-        .ok_or("Invalid input - expected operationSpec")?;
-    let operation_coef: Result<MonkeyOp, String> = match operation_spec.as_rule() {
+    let operation_spec = pairs.next().ok_or_else(|| missing("operationSpec"))?;
+    let operation_coef: Result<MonkeyOp, AocError> = match operation_spec.as_rule() {
         Rule::operationSpec => {
-            let mut operation_params = operation_spec.into_inner();
+            let mut operation_params = operation_spec.clone().into_inner();
             let operator = operation_params
                 .next()
-                .ok_or("Invalid input, operation_spec requires operation Operator (+ | *) ")?
+                .ok_or_else(|| {
+                    error_at(
+                        &operation_spec,
+                        "Invalid input, operation_spec requires operation Operator (+ | *)",
+                    )
+                })?
                 .as_str()
                 .to_string();
 
-            let coef: Result<MonkeyOpArg, String> = {
-                let param_p = operation_params
-                    .next()
-                    .ok_or("Invalid input, operation_spec needs operationCoef ")?;
+            let coef: Result<MonkeyOpArg, AocError> = {
+                let param_p = operation_params.next().ok_or_else(|| {
+                    error_at(
+                        &operation_spec,
+                        "Invalid input, operation_spec needs operationCoef",
+                    )
+                })?;
                 match param_p.as_rule() {
-                    Rule::operationIntArg => Ok(MonkeyOpArg::IntArg(
-                        param_p
-                            .as_str()
-                            .parse::<u64>()
-                            .map_err(|e| format!("{}", e))?,
-                    )),
+                    Rule::operationIntArg => Ok(MonkeyOpArg::IntArg(number::<u64>(&param_p)?)),
                     Rule::operationOldSelfArg => Ok(MonkeyOpArg::Old),
-                    _ => Err("Invalid input, operationSpec operationArg".into()),
+                    _ => Err(error_at(
+                        &param_p,
+                        "Invalid input, operationSpec operationArg",
+                    )),
                 }
             };
 
             Ok(MonkeyOp::new(operator, coef?))
         }
-        _ => Err("Invalid input, expected ASCII_DIGIT+ representing monkeyId".into()),
+        _ => Err(error_at(
+            &operation_spec,
+            "Invalid input, expected operationSpec",
+        )),
     };
 
-    let test_spec = pairs.next().ok_or("Invalid input - expected testSpec")?;
-    let monkey_test: Result<MonkeyTest, String> = match test_spec.as_rule() {
+    let test_spec = pairs.next().ok_or_else(|| missing("testSpec"))?;
+    let monkey_test: Result<MonkeyTest, AocError> = match test_spec.as_rule() {
         Rule::testSpec => {
-            let digits: Result<Vec<u64>, String> = test_spec
-                .into_inner()
-                .map(|d| d.as_str().parse::<u64>().map_err(|e| format!("{}", e)))
-                .collect();
-            let digits = digits?;
-            Ok(MonkeyTest::new(
-                digits[0],
-                digits[1] as usize,
-                digits[2] as usize,
-            ))
+            let digits: Vec<Pair<Rule>> = test_spec.clone().into_inner().collect();
+            match &digits[..] {
+                [divisor, truthy, falsy] => {
+                    let target = |p: &Pair<Rule>| {
+                        let id = number::<usize>(p)?;
+                        if id >= monkey_count {
+                            return Err(error_at(
+                                p,
+                                format!(
+                                    "unknown target monkey, there are only {} monkeys",
+                                    monkey_count
+                                ),
+                            ));
+                        }
+                        Ok(id)
+                    };
+                    let divisor_value = number::<u64>(divisor)?;
+                    if divisor_value == 0 {
+                        return Err(error_at(divisor, "divisor must be positive"));
+                    }
+                    Ok(MonkeyTest::new(
+                        divisor_value,
+                        target(truthy)?,
+                        target(falsy)?,
+                    ))
+                }
+                _ => Err(error_at(
+                    &test_spec,
+                    "Invalid input, testSpec needs a divisor and 2 target monkeys",
+                )),
+            }
         }
-        _ => Err("Invalid input, expected ASCII_DIGIT+ representing monkeyId".into()),
+        _ => Err(error_at(&test_spec, "Invalid input, expected testSpec")),
     };
 
-    // println!("MonkeyId: {}, starting_items: {:?}, monkey_test: {:?}", m_id?, starting_items?, monkey_test?);
     Ok(Monkey::new(
         m_id?,
         starting_items?,
@@ -137,7 +184,7 @@ mod parser_test {
 
         let res = parse_input(test_spec);
         println!("res={:?}", res);
-        assert_eq!(res.is_ok(), true);
+        assert!(res.is_ok());
         let monkeys = res.unwrap();
         assert_eq!(monkeys.len(), 4);
     }
@@ -151,13 +198,27 @@ mod parser_test {
           If true: throw to monkey 2
           If false: throw to monkey 3";
 
-        let res = parse_monkey(
+        let spec = || {
             MonkeySpecParser::parse(Rule::monkeySpec, input)
                 .unwrap()
                 .next()
-                .unwrap(),
-        );
+                .unwrap()
+        };
+        let res = parse_monkey(spec(), 4);
         println!("res={:?}", res);
-        assert_eq!(res.is_ok(), true);
+        assert!(res.is_ok());
+        assert_eq!(
+            parse_monkey(spec(), 3).unwrap_err().to_string(),
+            "parse error at line 6, column 37: unknown target monkey, there are only 3 monkeys, got: '3'"
+        );
+        let divisible_by_0 = input.replace("by 23", "by 0");
+        let p = MonkeySpecParser::parse(Rule::monkeySpec, &divisible_by_0)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            parse_monkey(p, 4).unwrap_err().to_string(),
+            "parse error at line 4, column 28: divisor must be positive, got: '0'"
+        );
     }
 }
@@ -1,240 +1,88 @@
-use core::fmt;
-use itertools::Itertools;
-use std::{
-    collections::{BinaryHeap, HashMap, HashSet},
-    error::Error,
+use crate::{
+    grid::{Grid, Pos},
+    search, Answer, AocError, Solution,
 };
 
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
-struct Vertex {
-    i: usize,
-    letter: char,
+pub struct HeightMap {
+    heights: Grid<char>,
+    start: Pos,
+    target: Pos,
 }
 
-impl fmt::Display for Vertex {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}", self.letter, self.i)
-    }
-}
-
-impl Vertex {
-    fn new(i: usize, letter: char) -> Vertex {
-        Vertex { i, letter }
-    }
-
-    fn height(&self) -> i32 {
-        match self.letter {
-            'S' => 'a' as i32,
-            'E' => 'z' as i32,
-            _ => self.letter as i32,
-        }
-    }
-    fn height_diff(&self, other: &Vertex) -> i32 {
-        other.height() - self.height()
-    }
-}
-
-struct Graph {
-    adjacency_list: HashMap<Vertex, Vec<(Vertex, usize)>>,
-    start: Vertex,
-    target: Vertex,
-}
-
-impl Graph {
-    fn adjacent_nodes(
-        vs: &Vec<Vertex>,
-        v_idx: usize,
-        cols: usize,
-        climbing_down: bool,
-    ) -> Vec<(Vertex, usize)> {
-        let v = &vs[v_idx];
-        let vs_length = vs.len();
-        let vx = v_idx % cols;
-        let vy = v_idx / cols;
-        let max_rows = vs_length / cols;
-
-        let ns = vec![(vx as i32) - 1, vx as i32, (vx as i32) + 1]
-            .into_iter()
-            .filter(|&x| x >= 0)
-            .map(|x| x as usize)
-            .filter(|&x| x < cols)
-            .flat_map(|x| {
-                // println!("x:{}", x);
-                vec![(vy as i32) - 1, vy as i32, (vy as i32) + 1]
-                    .into_iter()
-                    .filter(|&y| y >= 0)
-                    .map(|y| y as usize)
-                    .filter(|&y| y < max_rows)
-                    .map(move |y| (x, y))
-                    .filter(|(x, y)| !(x == &vx && y == &vy))
-                    .filter(|(x, y)| x.abs_diff(vx) + y.abs_diff(vy) == 1)
-                    .map(|(x, y)| {
-                        // println!("({}, {}) -> {}", x, y,  x + y*self.cols);
-                        let new_v = vs[x + y * cols];
-                        let height_diff = v.height_diff(&new_v);
-                        (new_v, height_diff)
-                    })
-                    // .filter(|(_, hdiff)| )
-                    .filter(|(_, hdiff)| {
-                        if climbing_down {
-                            *hdiff > -2
-                        } else {
-                            *hdiff < 2
-                        }
-                    })
-                    .map(|(v, _)| (v, 1)) // distance is always 1, if height differnce is at most 1 higher
-            })
-            .collect_vec();
-
-        ns
-    }
-
-    fn new(ls: &str, start_letter: char, target_letter: char) -> Result<Graph, String> {
-        let cols = ls
-            .find('\n')
-            .ok_or("Invalid input - single line tree grid is not a grid")?;
-        let vertices = ls
-            .chars()
-            .filter(|&c| c != '\n')
-            .enumerate()
-            .map(|(i, c)| Vertex::new(i, c))
-            .collect::<Vec<Vertex>>();
-        let climbing_down = match (start_letter, target_letter) {
-            ('S', 'E') => false,
-            ('E', 'S') => true,
-            _ => return Err(format!("Invalid start_letter or target_letter (or both), allowed values: ['S' | 'E'], got: start_letter:{}, target_letter:{}", 
-            start_letter, target_letter)),
+impl HeightMap {
+    fn new(ls: &str) -> Result<HeightMap, AocError> {
+        let letters = Grid::parse(ls, |c| match c {
+            'a'..='z' | 'S' | 'E' => Ok(c),
+            _ => Err("invalid height, expected a-z, S or E".to_string()),
+        })?;
+        let find = |letter: char| {
+            letters
+                .iter()
+                .find(|(_, &c)| c == letter)
+                .map(|(pos, _)| pos)
+                .ok_or(format!("Cannot find grid field - '{}'", letter))
         };
-
-        let adjacency_list: HashMap<Vertex, Vec<(Vertex, usize)>> = vertices
-            .iter()
-            .enumerate()
-            .map(|(i, &v)| {
-                let ns = Graph::adjacent_nodes(&vertices, i, cols, climbing_down);
-                (v, ns)
-            })
-            .collect();
-
-        let &target = vertices
-            .iter()
-            .filter(|&v| v.letter == target_letter)
-            .take(1)
-            .next()
-            .ok_or("Cannot find target node index, i.e. grid field - 'E'")?;
-
-        let &start = vertices
-            .iter()
-            .filter(|v| v.letter == start_letter)
-            .take(1)
-            .next()
-            .ok_or("Cannot find target node index, i.e. grid field - 'E'")?;
-
-        Ok(Graph {
-            adjacency_list,
+        let start = find('S')?;
+        let target = find('E')?;
+
+        let heights = letters.map(|_, &c| match c {
+            'S' => 'a',
+            'E' => 'z',
+            _ => c,
+        });
+        Ok(HeightMap {
+            heights,
             start,
             target,
         })
     }
-}
-struct PathDistance {
-    target: Vertex,
-    distance: usize,
-}
-impl PathDistance {
-    fn new(v: Vertex, d: usize) -> PathDistance {
-        PathDistance {
-            target: v,
-            distance: d,
-        }
-    }
-}
 
-impl Ord for PathDistance {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // since we'll be looking for MIN distance, need to swap around self and other
-        other.distance.cmp(&self.distance)
+    fn height(&self, pos: Pos) -> i32 {
+        self.heights.get(pos).map_or(0, |&c| c as i32)
     }
-}
 
-impl PartialOrd for PathDistance {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        other.distance.partial_cmp(&self.distance)
+    // fields reachable in one step, i.e. at most 1 higher
+    fn climbable(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.heights
+            .neighbours4(pos)
+            .filter(move |&n| self.height(n) - self.height(pos) < 2)
     }
-}
 
-impl PartialEq for PathDistance {
-    fn eq(&self, other: &Self) -> bool {
-        self.target == other.target && self.distance == other.distance
+    fn shortest_path(&self, starts: impl IntoIterator<Item = Pos>) -> Result<usize, AocError> {
+        let (target_row, target_col) = self.target;
+        // every step costs 1 and moves by 1 row or column, so manhattan distance never overestimates
+        let distance_left = |&(row, col): &Pos| row.abs_diff(target_row) + col.abs_diff(target_col);
+        let path = search::astar(
+            starts,
+            |&pos| self.climbable(pos).map(|n| (n, 1)),
+            distance_left,
+            |&pos| pos == self.target,
+        )
+        .ok_or("no path found to E")?;
+        Ok(path.cost)
     }
 }
 
-impl Eq for PathDistance {}
-
-fn dijkstra(g: &Graph) -> HashMap<Vertex, usize> {
-    let mut distances = HashMap::new();
-    let mut visited = HashSet::new();
-    let mut to_visit = BinaryHeap::new();
-
-    distances.insert(g.start, 0);
-    to_visit.push(PathDistance::new(g.start, 0));
-
-    while let Some(PathDistance { target, distance }) = to_visit.pop() {
-        if target == g.target {
-            return distances;
-        }
-
-        if !visited.insert(target) {
-            // if insert failed, we already visited this node
-            continue;
-        }
+pub struct Day12;
 
-        if let Some(neighbours) = g.adjacency_list.get(&target) {
-            for (neighbour, n_distance) in neighbours {
-                let new_distance = distance + n_distance;
+impl Solution for Day12 {
+    type Input = HeightMap;
 
-                let is_shorter = distances
-                    .get(&neighbour)
-                    .map_or(true, |&current_dist| new_distance < current_dist);
-
-                if is_shorter {
-                    distances.insert(*neighbour, new_distance);
-                    to_visit.push(PathDistance::new(*neighbour, new_distance));
-                }
-            }
-        }
+    fn parse(&self, input: &str) -> Result<HeightMap, AocError> {
+        HeightMap::new(input)
     }
 
-    distances
-}
-//https://codereview.stackexchange.com/a/202879
-
-pub(crate) fn result(input: String) -> Result<(), Box<dyn Error>> {
-    // let v = Vertex::new(39, 'l', 8);
-    // let v2=  Vertex {
-    //     i: 4, x: 0, y: 4, letter: 'm', cols: 8,
-    // };
-    // let h = v.height();
-    // let h2 = v2.height();
-
-    // let dist = v.distance(&v2);
-    // let ns = v.neighbours(40);
-
-    let g1 = Graph::new(&input, 'S', 'E')?;
-    // let ns = g1.adjacency_list.get(&g1.target);
-    // let to_target2 = g.adjacency_list.get(&Vertex::new(3475, 'y'));
-    let distances = dijkstra(&g1);
-
-    let target_distance = distances.get(&g1.target);
-    println!("Part 1 - distance:{:?}", target_distance);
-
-    let g2 = Graph::new(&input, 'E', 'S')?;
-    let reverse_distances = dijkstra(&g2);
+    fn part1(&self, map: &HeightMap) -> Result<Answer, AocError> {
+        Ok(map.shortest_path([map.start])?.into())
+    }
 
-    let start_points = reverse_distances
-        .iter()
-        .filter(|(&k, _)| k.letter == 'a')
-        .sorted_by(|a, b| a.1.cmp(b.1))
-        .collect_vec();
-    println!("Part 2 - distance: {}", start_points[0].1);
-    Ok(())
+    fn part2(&self, map: &HeightMap) -> Result<Answer, AocError> {
+        // one search starting from all the lowest fields at once finds the closest of them
+        let lowest = map
+            .heights
+            .iter()
+            .filter(|(_, &c)| c == 'a')
+            .map(|(pos, _)| pos);
+        Ok(map.shortest_path(lowest)?.into())
+    }
 }
@@ -3,7 +3,8 @@ use itertools::Itertools;
 use pest::iterators::Pair;
 use pest::Parser;
 use std::collections::HashSet;
-use std::error::Error;
+
+use crate::{Answer, AocError, Solution};
 
 #[derive(Debug)]
 enum DataGram {
@@ -21,7 +22,7 @@ impl fmt::Display for DataGram {
 }
 
 #[derive(Debug)]
-struct PacketPair {
+pub struct PacketPair {
     index: usize,
     left: DataGram,
     right: DataGram,
@@ -40,9 +41,9 @@ impl PacketPair {
         use itertools::EitherOrBoth::*;
         match (left, right) {
             (DataGram::Int(l), DataGram::Int(r)) => {
-                if l < &r {
+                if l < r {
                     Some(true)
-                } else if l > &r {
+                } else if l > r {
                     Some(false)
                 } else {
                     None
@@ -75,91 +76,104 @@ impl PacketPair {
 struct PacketParser;
 
 impl DataGram {
-    fn parse_packet(p: Pair<Rule>) -> Result<DataGram, String> {
+    fn parse_packet(p: Pair<'_, Rule>) -> Result<DataGram, AocError> {
+        let error = |p: &Pair<'_, Rule>, message: String| {
+            AocError::parse(p.as_span().start_pos().line_col().1, p.as_str(), message)
+        };
         match p.as_rule() {
-            Rule::packet => DataGram::parse_packet(p.into_inner().next().ok_or("empty packet")?),
-            Rule::integer => Ok(DataGram::Int(
-                p.as_str().parse::<u32>().map_err(|e| format!("{}", e))?,
-            )),
+            Rule::packet => {
+                let inner = p.clone().into_inner().next();
+                DataGram::parse_packet(inner.ok_or_else(|| error(&p, "empty packet".into()))?)
+            }
+            Rule::integer => {
+                Ok(DataGram::Int(p.as_str().parse::<u32>().map_err(|e| {
+                    error(&p, format!("invalid integer, {}", e))
+                })?))
+            }
             Rule::list => {
-                let items: Result<Vec<DataGram>, String> =
-                    p.into_inner().map(|p| DataGram::parse_packet(p)).collect();
-                items.map(|xs| DataGram::List(xs))
+                let items: Result<Vec<DataGram>, AocError> =
+                    p.into_inner().map(DataGram::parse_packet).collect();
+                items.map(DataGram::List)
             }
-            _ => Err(format!(
-                "unexpected input, expected: [packet | list | integer], got: {}",
-                p.as_str()
+            _ => Err(error(
+                &p,
+                "unexpected input, expected: [packet | list | integer]".into(),
             )),
         }
     }
 
-    fn new(l: &str) -> Result<DataGram, String> {
-        let mut p = PacketParser::parse(Rule::packet, l).map_err(|e| format!("{}", e))?;
+    fn new(l: &str) -> Result<DataGram, AocError> {
+        let mut p = PacketParser::parse(Rule::packet, l)?;
         DataGram::parse_packet(p.next().ok_or("empty top level packet")?)
     }
 }
 
-pub fn result(ls: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let lines: Result<Vec<PacketPair>, String> = ls
-        .into_iter()
-        .chunks(3)
-        .into_iter()
-        .enumerate()
-        .map(|(i, ch)| {
-            let pair: Result<Vec<DataGram>, String> =
-                ch.take(2).map(|s| DataGram::new(&s)).collect();
-            pair.map(|p| {
-                let mut p = p.into_iter();
-                PacketPair::new(
-                    i + 1,
-                    p.next().unwrap(), // todo maybe find a better way
-                    p.next().unwrap(),
-                )
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<PacketPair>;
+
+    fn parse(&self, ls: &str) -> Result<Vec<PacketPair>, AocError> {
+        let lines: Result<Vec<PacketPair>, AocError> = ls
+            .lines()
+            .chunks(3)
+            .into_iter()
+            .enumerate()
+            .map(|(i, ch)| {
+                let first_line = i * 3 + 1;
+                let pair: Result<Vec<DataGram>, AocError> = ch
+                    .take(2)
+                    .enumerate()
+                    .map(|(j, l)| DataGram::new(l).map_err(|e| e.on_line(first_line + j)))
+                    .collect();
+                match <[DataGram; 2]>::try_from(pair?) {
+                    Ok([left, right]) => Ok(PacketPair::new(i + 1, left, right)),
+                    Err(_) => {
+                        Err(
+                            AocError::parse(1, "", "incomplete packet pair, expected 2 packets")
+                                .on_line(first_line + 1),
+                        )
+                    }
+                }
             })
-        })
-        .collect();
-
-    let pairs = lines?;
-    // println!("inputs: {:?}", pairs);
-
-    let right_ordered = pairs.iter().filter(|&p| p.is_right_order());
-    // println!("right_ordered: {:?}", right_ordered);
-    let res: usize = right_ordered.map(|p| p.index).sum();
-
-    println!("Part1 - result: {}", res);
-
-    // for part 2, sort pairs with is_right_order turned into comparator
-    // but first add 2 more items divider_packets
-    // [[2]]
-    // [[6]]
-    let divider_packets = vec![
-        DataGram::List(vec![DataGram::List(vec![DataGram::Int(2)])]),
-        DataGram::List(vec![DataGram::List(vec![DataGram::Int(6)])]),
-    ];
-    //  first would need to flatten list of PacketPairs into list of DataGrams?
-    let flattened_packets = pairs
-        .into_iter()
-        .flat_map(|p| vec![p.left, p.right])
-        .chain(divider_packets)
-        .sorted_by(|a, b| match PacketPair::are_ordered(a, b) {
-            Some(true) => std::cmp::Ordering::Less,
-            Some(false) => std::cmp::Ordering::Greater,
-            None => std::cmp::Ordering::Equal,
-        });
-    // flattened_packets.for_each(|p| {
-    //     println!("{}", p);
-    // });
-    let divider_packets = vec![
-        DataGram::List(vec![DataGram::List(vec![DataGram::Int(2)])]),
-        DataGram::List(vec![DataGram::List(vec![DataGram::Int(6)])]),
-    ];
-    let divider_set = HashSet::<_>::from_iter(divider_packets.iter().map(|p| format!("{}", p)));
-    let divider_indices: usize = flattened_packets
-        .enumerate()
-        .filter(|(_, p)| divider_set.contains(&format!("{}", p)))
-        .map(|(i, _)| i + 1)
-        .product();
-
-    println!("Part2 - {}", divider_indices);
-    Ok(())
+            .collect();
+
+        lines
+    }
+
+    fn part1(&self, pairs: &Vec<PacketPair>) -> Result<Answer, AocError> {
+        let right_ordered = pairs.iter().filter(|&p| p.is_right_order());
+        // println!("right_ordered: {:?}", right_ordered);
+        let res: usize = right_ordered.map(|p| p.index).sum();
+        Ok(res.into())
+    }
+
+    fn part2(&self, pairs: &Vec<PacketPair>) -> Result<Answer, AocError> {
+        // for part 2, sort pairs with is_right_order turned into comparator
+        // but first add 2 more items divider_packets
+        // [[2]]
+        // [[6]]
+        let divider_packets = [
+            DataGram::List(vec![DataGram::List(vec![DataGram::Int(2)])]),
+            DataGram::List(vec![DataGram::List(vec![DataGram::Int(6)])]),
+        ];
+        let divider_set = HashSet::<_>::from_iter(divider_packets.iter().map(|p| format!("{}", p)));
+        //  first would need to flatten list of PacketPairs into list of DataGrams?
+        let flattened_packets = pairs
+            .iter()
+            .flat_map(|p| [&p.left, &p.right])
+            .chain(divider_packets.iter())
+            .sorted_by(|a, b| match PacketPair::are_ordered(a, b) {
+                Some(true) => std::cmp::Ordering::Less,
+                Some(false) => std::cmp::Ordering::Greater,
+                None => std::cmp::Ordering::Equal,
+            });
+        let divider_indices: usize = flattened_packets
+            .enumerate()
+            .filter(|(_, p)| divider_set.contains(&format!("{}", p)))
+            .map(|(i, _)| i + 1)
+            .product();
+
+        Ok(divider_indices.into())
+    }
 }
@@ -1,3 +1,4 @@
-pub use day13::result;
+pub use day13::Day13;
 
+#[allow(clippy::module_inception)]
 mod day13;
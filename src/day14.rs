@@ -1,52 +1,46 @@
 use core::fmt;
 use itertools::Itertools;
-use std::{collections::HashSet, error::Error, num::ParseIntError};
+use std::collections::HashSet;
+
+use crate::{
+    error::parse_lines,
+    geom::{BoundingBox, Point},
+    Answer, AocError, Solution,
+};
 
 struct RockPath {
-    path_lines: Vec<((i32, i32), (i32, i32))>,
-    lowest_level: i32,
-    left_most: i32,
-    right_most: i32,
+    // every straight line of the path, as the box spanned by its ends
+    path_lines: Vec<BoundingBox>,
+    bounds: BoundingBox,
 }
 
 impl RockPath {
-    fn is_blocked(&self, (p_x, p_y): (i32, i32)) -> bool {
-        self.path_lines.iter().any(|((e_x, e_y), (s_x, s_y))| {
-            let r = (s_x <= &p_x && &p_x <= e_x || e_x <= &p_x && &p_x <= s_x)
-                && (e_y <= &p_y && &p_y <= s_y || s_y <= &p_y && &p_y <= e_y);
-            //    println!("is_blocked: {}, because ({}, {}) is between ({},{}) and ({},{})", r, p_x, p_y, s_x, s_y, e_x, e_y);
-            r
-        })
+    fn is_blocked(&self, p: Point) -> bool {
+        self.path_lines.iter().any(|l| l.contains(p))
     }
 
-    fn new(p: Vec<(i32, i32)>) -> RockPath {
-        let lowest_level = *p.iter().map(|(_, y)| y).max().unwrap();
-        let left_most = *p.iter().map(|(x, _)| x).filter(|&x| x > &i32::MIN).min().unwrap();
-        let right_most = *p.iter().map(|(x, _)| x).filter(|&x| x < &i32::MAX).max().unwrap();
+    fn new(p: &[Point]) -> RockPath {
+        let bounds =
+            BoundingBox::from_points(p.iter().copied()).expect("rock paths are never empty");
         let path_lines = p
             .iter()
             .zip(p.iter().skip(1))
-            .into_iter()
-            .map(|(&s, &e)| (s, e))
-            .collect::<Vec<((i32, i32), (i32, i32))>>();
-        RockPath {
-            path_lines,
-            lowest_level,
-            left_most,
-            right_most,
-        }
+            .map(|(&s, &e)| BoundingBox::from_points([s, e]).expect("a line has 2 ends"))
+            .collect::<Vec<BoundingBox>>();
+        RockPath { path_lines, bounds }
     }
 }
 
-type Sand = (i32, i32);
+type Sand = Point;
 
 struct Cave {
-    sand_units: HashSet<(i32, i32)>,
+    sand_units: HashSet<Point>,
     rock_paths: Vec<RockPath>,
     sand_unit_total: usize,
     bottom_level: i32,
     left_most: i32,
     right_most: i32,
+    has_floor: bool,
 }
 
 impl fmt::Display for Cave {
@@ -55,7 +49,6 @@ impl fmt::Display for Cave {
             f,
             "{}",
             (self.left_most..self.right_most + 1)
-                .into_iter()
                 .map(|c| if c == self.left_most || c == self.right_most {
                     c.to_string()
                 } else {
@@ -65,14 +58,25 @@ impl fmt::Display for Cave {
         )
         .expect("failed to write to fmt:Formatter");
 
-        let left_bound =  self.left_most.min(self.sand_units.iter().map(|(x,_)| *x).min().or(Some(self.left_most)).unwrap());
-        let right_bound =  self.right_most.min(self.sand_units.iter().map(|(x,_)| *x).max().or(Some(self.right_most)).unwrap());
+        let left_bound = self.left_most.min(
+            self.sand_units
+                .iter()
+                .map(|p| p.x)
+                .min()
+                .unwrap_or(self.left_most),
+        );
+        let right_bound = self.right_most.min(
+            self.sand_units
+                .iter()
+                .map(|p| p.x)
+                .max()
+                .unwrap_or(self.right_most),
+        );
         for y in 0..self.bottom_level + 3 {
-            let line = (left_bound-5..right_bound+15)
-                .into_iter()
-                .map(|x| match x {
-                    x if self.sand_units.contains(&(x, y)) => 'o',
-                    x if self.rock_paths.iter().any(|p| p.is_blocked((x, y))) => '#',
+            let line = (left_bound - 5..right_bound + 15)
+                .map(|x| match Point::new(x, y) {
+                    p if self.sand_units.contains(&p) => 'o',
+                    p if self.rock_paths.iter().any(|r| r.is_blocked(p)) => '#',
                     _ => '.',
                 })
                 .join("");
@@ -84,57 +88,55 @@ impl fmt::Display for Cave {
 
 impl Cave {
     fn new_sand() -> Sand {
-        (500, 0)
+        Point::new(500, 0)
     }
 
-    fn move_sand(&mut self) -> Sand {
+    // returns None once sand falls past the lowest rock into the abyss (only possible without a floor)
+    fn move_sand(&mut self) -> Option<Sand> {
         let mut s = Cave::new_sand();
         let mut n_s = self.next_sand_pos(s);
         while s != n_s && self.above_bottom_rock(n_s) {
-            //  println!("sand: {:?}, next_sand: {:?}", s, n_s);
-            //  self.sand_units.insert(s);
-            //  println!("{}", self);
-            //  self.sand_units.remove(&s);
             let tmp = n_s;
             n_s = self.next_sand_pos(n_s);
             s = tmp;
         }
+        if !self.has_floor && !self.above_bottom_rock(n_s) {
+            return None;
+        }
         self.sand_units.insert(n_s);
         self.sand_unit_total += 1;
-        n_s
+        Some(n_s)
     }
 
-    fn next_sand_pos(&self, (x, y): Sand) -> Sand {
-        if self.is_free((x, y + 1)) {
-            // go down
-            (x, y + 1)
-        } else if self.is_free((x - 1, y + 1)) {
-            // go left
-            (x - 1, y + 1)
-        } else if self.is_free((x + 1, y + 1)) {
-            // go right
-            (x + 1, y + 1)
-        } else {
-            (x, y)
-        }
+    fn next_sand_pos(&self, s: Sand) -> Sand {
+        // go down, then down left, then down right
+        [Point::new(0, 1), Point::new(-1, 1), Point::new(1, 1)]
+            .into_iter()
+            .map(|d| s + d)
+            .find(|&n| self.is_free(n))
+            .unwrap_or(s)
     }
 
-    fn is_free(&self, y: (i32, i32)) -> bool {
-        !self.sand_units.contains(&y) && self.rock_paths.iter().all(|p| !p.is_blocked(y))
+    fn is_free(&self, p: Point) -> bool {
+        !self.sand_units.contains(&p) && self.rock_paths.iter().all(|r| !r.is_blocked(p))
     }
 
-    fn above_bottom_rock(&self, (_, y): (i32, i32)) -> bool {
-        y <= self.bottom_level
+    fn above_bottom_rock(&self, p: Point) -> bool {
+        p.y <= self.bottom_level
     }
 
-    pub(crate) fn new(paths: Vec<Vec<(i32, i32)>>) -> Cave {
-        let mut rock_paths: Vec<RockPath> = paths.into_iter().map(|p| RockPath::new(p)).collect();
-        let bottom_level = rock_paths.iter().map(|rp| rp.lowest_level).max().unwrap();
-        let left_most = rock_paths.iter().map(|rp| rp.left_most).min().unwrap();
-        let right_most = rock_paths.iter().map(|rp| rp.right_most).max().unwrap();
-        // bottom floor
-        rock_paths.push(RockPath::new(vec![(i32::MIN,bottom_level+2), (i32::MAX, bottom_level+2)]));
-
+    pub(crate) fn new(paths: &[Vec<Point>], has_floor: bool) -> Cave {
+        let mut rock_paths: Vec<RockPath> = paths.iter().map(|p| RockPath::new(p)).collect();
+        let bottom_level = rock_paths.iter().map(|rp| rp.bounds.max.y).max().unwrap();
+        let left_most = rock_paths.iter().map(|rp| rp.bounds.min.x).min().unwrap();
+        let right_most = rock_paths.iter().map(|rp| rp.bounds.max.x).max().unwrap();
+        if has_floor {
+            // bottom floor
+            rock_paths.push(RockPath::new(&[
+                Point::new(i32::MIN, bottom_level + 2),
+                Point::new(i32::MAX, bottom_level + 2),
+            ]));
+        }
 
         Cave {
             sand_units: HashSet::new(),
@@ -143,50 +145,65 @@ impl Cave {
             bottom_level,
             left_most,
             right_most,
+            has_floor,
         }
     }
 }
 
-fn parse_point(p: &str) -> Result<(i32, i32), String> {
+fn parse_point(p: &str) -> Result<Point, AocError> {
     let ps = p
-        .split(",")
-        .map(|i| i.parse::<i32>())
-        .collect::<Result<Vec<i32>, ParseIntError>>();
-
-    ps.map_err(|s| format!("{}", s))?
-        .into_iter()
-        .collect_tuple()
-        .ok_or(format!("failed to parse input line: {}", p))
-}
-
-pub(crate) fn result(lines: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let paths: Result<Vec<Vec<(i32, i32)>>, String> = lines
-        .iter()
-        .map(|l| {
-            l.split(" -> ")
-                .map(parse_point)
-                .collect::<Result<Vec<(i32, i32)>, String>>()
+        .split(',')
+        .map(|i| {
+            i.parse::<i32>()
+                .map_err(|e| AocError::parse_token(p, i, format!("invalid coordinate, {}", e)))
         })
-        .collect();
+        .collect::<Result<Vec<i32>, AocError>>();
 
-    // println!("input: {:?}", paths);
- 
-    let mut c: Cave = Cave::new(paths?);
+    ps?.into_iter()
+        .collect_tuple::<(i32, i32)>()
+        .map(Point::from)
+        .ok_or_else(|| AocError::parse(1, p, "invalid point, expected x,y"))
+}
+
+pub struct Day14;
 
-    loop {
-        // println!("next sand enters");
-        let s = c.move_sand();
-        // print!("\x1B[2J");  // clear terminal
-        //  println!("{}", c);
+impl Solution for Day14 {
+    type Input = Vec<Vec<Point>>;
 
-        if  s == (500,0) {
-            println!("breaking");
-            break;
+    fn parse(&self, lines: &str) -> Result<Vec<Vec<Point>>, AocError> {
+        let paths = parse_lines(lines, |l| {
+            l.split(" -> ")
+                .map(|p| parse_point(p).map_err(|e| e.within(l, p)))
+                .collect()
+        })?;
+        if paths.is_empty() {
+            return Err("no rock paths found, the cave needs at least one".into());
         }
+        Ok(paths)
     }
 
-    //println!("{}", c);
-    println!("Part1 result: {}", c.sand_unit_total);
+    fn part1(&self, paths: &Vec<Vec<Point>>) -> Result<Answer, AocError> {
+        let mut c: Cave = Cave::new(paths, false);
+        while c.move_sand().is_some() {}
+        //println!("{}", c);
+        Ok(c.sand_unit_total.into())
+    }
+
+    fn part2(&self, paths: &Vec<Vec<Point>>) -> Result<Answer, AocError> {
+        let mut c: Cave = Cave::new(paths, true);
+
+        loop {
+            // println!("next sand enters");
+            let s = c.move_sand();
+            // print!("\x1B[2J");  // clear terminal
+            //  println!("{}", c);
 
-    Ok(())
+            if s == Some(Cave::new_sand()) {
+                break;
+            }
+        }
+
+        //println!("{}", c);
+        Ok(c.sand_unit_total.into())
+    }
 }
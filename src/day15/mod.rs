@@ -1,181 +1,244 @@
-use std::{error::Error, num::ParseIntError, collections::HashSet};
 use itertools::Itertools;
 use regex::Regex;
+use std::collections::HashSet;
 
-
-type Coord = (i32, i32);
-
+use crate::{error::parse_lines, geom::Point, interval::Interval, Answer, AocError, Solution};
 
 #[derive(Debug)]
-struct Sensor {
-    pos: Coord,
-    closest_beacon: Coord,
+pub struct Sensor {
+    pos: Point,
+    closest_beacon: Point,
     covered_range: u32,
-} 
-
-#[cfg(test)]
-mod sensor_test {
-    use super::Sensor;
-
-
-    #[test]
-    fn sensor_perimiter_test_range_zero() {
-        let s = Sensor::new((0,0), (0,0));
-
-        let expected_perimiter = vec![(0,-1), (1,0), (0,1), (-1,0)];
-        assert_eq!(expected_perimiter, s.sensor_perimiter(-2, 2));
-    }
-
-    #[test]
-    fn sensor_perimiter_test_range_one() {
-        let s = Sensor::new((0,0), (0,1));
-
-        let expected_perimiter = vec![(0,-2), (1, -1), (2,0), (1,1), (0,2), (-1,1), (-2, 0), (-1, -1)];
-        assert_eq!(expected_perimiter, s.sensor_perimiter(-2, 2));
-    }
-
-    #[test]
-    fn sensor_perimiter_test_range_one_with_bounds() {
-        let s = Sensor::new((0,0), (0,1));
-
-        let expected_perimiter = vec![(2,0), (1,1), (0,2)];
-        assert_eq!(expected_perimiter, s.sensor_perimiter(0, 2));
-    }
 }
 
 impl Sensor {
-    //return list of Coords just outside Sensor's range
-    fn sensor_perimiter(&self, lower_bound: i32, upper_bound: i32) -> Vec<Coord> {
-        let (s_x, s_y) = self.pos;
+    //return list of Points just outside Sensor's range
+    fn sensor_perimiter(&self, lower_bound: i32, upper_bound: i32) -> Vec<Point> {
+        let Point { x: s_x, y: s_y } = self.pos;
         let perimiter_range = (self.covered_range as i32) + 1;
-        let min_x = s_x-perimiter_range;
+        let min_x = s_x - perimiter_range;
         let max_x = s_x + perimiter_range;
-        let top_descending = (s_x..max_x+1).zip((s_y-perimiter_range)..s_y+1).collect_vec();
+        let top_descending = (s_x..max_x + 1)
+            .zip((s_y - perimiter_range)..s_y + 1)
+            .collect_vec();
         // println!("{:?}", top_descending);
-        let bottom_rising = (s_x..max_x).rev().zip((s_y+1)..(s_y+perimiter_range+1)).collect_vec();
+        let bottom_rising = (s_x..max_x)
+            .rev()
+            .zip((s_y + 1)..(s_y + perimiter_range + 1))
+            .collect_vec();
         // println!("{:?}", bottom_rising);
-        let bottom_descending =  (min_x..s_x).zip((s_y)..(s_y+perimiter_range)).collect_vec();
+        // walks right to left like bottom_rising, so the perimeter is listed in one clockwise pass
+        let bottom_descending = (min_x..s_x)
+            .rev()
+            .zip((s_y..(s_y + perimiter_range)).rev())
+            .collect_vec();
         // println!("{:?}", bottom_descending);
-        let top_rising = ((min_x+1)..s_x).zip(((s_y-perimiter_range)..s_y).rev()).collect_vec();
+        let top_rising = ((min_x + 1)..s_x)
+            .zip(((s_y - perimiter_range)..s_y).rev())
+            .collect_vec();
         // println!("{:?}", top_rising);
 
-        top_descending.into_iter()
-            .chain(bottom_rising.into_iter())
-            .chain(bottom_descending.into_iter())
-            .chain(top_rising.into_iter())
-            .filter(|(x,y)| lower_bound <= *x && x <= &upper_bound 
-                                && lower_bound <= *y && y <= &upper_bound)
+        top_descending
+            .into_iter()
+            .chain(bottom_rising)
+            .chain(bottom_descending)
+            .chain(top_rising)
+            .filter(|(x, y)| {
+                lower_bound <= *x && x <= &upper_bound && lower_bound <= *y && y <= &upper_bound
+            })
+            .map(Point::from)
             .collect()
     }
 
-    fn is_within_range(&self, c: Coord) -> bool {
-        Sensor::manhattan_distance(self.pos, c) <= self.covered_range
-    } 
+    fn is_within_range(&self, c: Point) -> bool {
+        self.pos.manhattan(c) <= self.covered_range
+    }
 
-    fn new(pos: Coord, closest_beacon: Coord) -> Sensor {
-        let covered_range = Sensor::manhattan_distance(pos, closest_beacon);
-        Sensor { pos, closest_beacon, covered_range }
+    fn new(pos: Point, closest_beacon: Point) -> Sensor {
+        let covered_range = pos.manhattan(closest_beacon);
+        Sensor {
+            pos,
+            closest_beacon,
+            covered_range,
+        }
     }
 
-    
-    fn parse(ls: Vec<String>) -> Result<Vec<Sensor>, ParseIntError> {
+    fn parse(ls: &str) -> Result<Vec<Sensor>, AocError> {
         let coord_re = Regex::new(r"x=(-?\d+), y=(-?\d+)").unwrap();
 
-        ls.into_iter().map(|l| {
-            let cords:Result<Vec<Coord>, ParseIntError> =
-            coord_re
-            .captures_iter(&l).map(|cap| 
-                Ok((cap[1].parse::<i32>()?, cap[2].parse::<i32>()?)))
-            .collect();
-            let cords = cords?;
-            Ok(Sensor::new(cords[0], cords[1]))
-        }).collect()
+        parse_lines(ls, |l| {
+            let coordinate = |m: regex::Match| {
+                m.as_str().parse::<i32>().map_err(|e| {
+                    AocError::parse(
+                        m.start() + 1,
+                        m.as_str(),
+                        format!("invalid coordinate, {}", e),
+                    )
+                })
+            };
+            let cords: Result<Vec<Point>, AocError> = coord_re
+                .captures_iter(l)
+                .map(|cap| {
+                    Ok(Point::new(
+                        coordinate(cap.get(1).unwrap())?,
+                        coordinate(cap.get(2).unwrap())?,
+                    ))
+                })
+                .collect();
+            match cords?[..] {
+                [sensor, beacon] => Ok(Sensor::new(sensor, beacon)),
+                _ => Err(AocError::parse(
+                    1,
+                    l,
+                    "expected sensor and closest beacon positions, i.e. x=2, y=18 ... x=-2, y=15",
+                )),
+            }
+        })
     }
 
-    fn manhattan_distance((x_a, y_a): Coord, (x_b, y_b): Coord) -> u32 {
-        x_b.abs_diff(x_a) + y_b.abs_diff(y_a)
+    // x range covered by this Sensor in row y, if any
+    fn row_coverage(&self, y: i32) -> Option<Interval> {
+        let Point { x: s_x, y: s_y } = self.pos;
+        let reach = self.covered_range as i64 - s_y.abs_diff(y) as i64;
+        if reach < 0 {
+            None
+        } else {
+            Some(Interval::new(s_x as i64 - reach, s_x as i64 + reach))
+        }
     }
 }
 
-pub(crate) fn result(ls: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let sensors: Result<Vec<Sensor>, ParseIntError> = Sensor::parse(ls);
-    let sensors = sensors?;
-    // println!("{:?}", sensors);
-
-    // check number of fields covered in y=10
-    
-
-    let min_x = sensors.iter().map(|s| s.pos.0 - (s.covered_range as i32)).min().ok_or("empty sensor list in min_x")?;
-    let max_x = sensors.iter().map(|s| s.pos.0 + (s.covered_range as i32)).max().ok_or("empty sensor list in max_x")?;
-    
-    // println!("min_x:{min_x}, max_x:{max_x}");
-    // let y = 2000000;
-
-    // let beacon_set = HashSet::<_>::from_iter(sensors.iter().map(|s| s.closest_beacon));
-
-    // let res: usize = (min_x..max_x+1).map(|x| (x, y)).filter(|&c| {
-    //     let covered =    sensors.iter().any(|s| s.is_within_range(c));
-    //     // println!("({},{}) is {}", c.0, c.1, covered);
-    //     covered
-    // })
-    //  .filter(|c| !beacon_set.contains(c))
-    // .count();
-
-    // println!("Part1 Result: {}", res);
-
-
-    let distr_max = 4000000;
-    // https://github.com/saulvaldelvira/AdventOfCode/blob/5ebda5ec175e15bdb42f217767592e68ae00a829/2022/Day15/puzzle15.c#L165
-
-
-    // let is_it = sensors
-    // .iter()
-    // .filter(|s| s.is_within_range((14,11)))
-    // .map(|s| (s, Sensor::manhattan_distance(s.pos, (14,11)), s.covered_range))
-    // .collect_vec();
-
-    // let all_perimiter = sensors.iter()
-    //     .map(|s| (s.pos, s.covered_range, s.sensor_perimiter(0, distr_max)));
-    //     //.for_each(|d| println!("{d:?}", ));
-
-        
-    // let ((s_x, s_y), r, perimiter) = &all_perimiter.collect_vec()[0];    
-
-    // let perimit_set = HashSet::<_>::from_iter(perimiter);
-
-    // let max_grid = 4000000;
-    // (0..max_grid).map(|y| {
-    //     (0..max_grid).map(|x| match (x,y) {
-    //         (x,y) if &x == s_x && &y == s_y => "S",
-    //         p if perimit_set.contains(&p) => "#",
-    //         _ => " "
-    //     }).join("")
-    // }).for_each(|l| println!("{l}"));
-
-    if let Some(distress_beacon) = sensors
-        .iter()
-        .map(|s|s.sensor_perimiter(0,distr_max)
-                            .iter()
-                            .skip_while(|&c| {
-                                let covered = sensors
-                                                        .iter()
-                                                        .any(|s| s.is_within_range(*c));
-                                //  println!("({},{}) is {}", c.0, c.1, covered);
-                                                        covered 
-                            })
-                            .map(|&c| c)
-                            .next())
-        .skip_while(|s| s.is_none())
-        .map(|c| c.unwrap())
-        .next() {
-        println!("Part2 Distress Beacon coords: {:?}", distress_beacon);
-        let (x, y) = distress_beacon;
+pub struct Day15 {
+    row: i32,
+    search_bound: i32,
+}
+
+impl Default for Day15 {
+    fn default() -> Self {
+        Day15 {
+            row: 2000000,
+            search_bound: 4000000,
+        }
+    }
+}
+
+impl Solution for Day15 {
+    type Input = Vec<Sensor>;
+
+    fn parse(&self, ls: &str) -> Result<Vec<Sensor>, AocError> {
+        Sensor::parse(ls)
+    }
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), AocError> {
+        match option {
+            "row" => {
+                self.row = value
+                    .parse()
+                    .map_err(|e| AocError::invalid_option(option, format!("{}", e)))?
+            }
+            "search_bound" => {
+                self.search_bound = value
+                    .parse()
+                    .map_err(|e| AocError::invalid_option(option, format!("{}", e)))?
+            }
+            _ => {
+                return Err(AocError::invalid_option(
+                    option,
+                    "unknown option, day15 supports [row, search_bound]",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn part1(&self, sensors: &Vec<Sensor>) -> Result<Answer, AocError> {
+        // check number of fields covered in y=row, merging covered ranges instead of checking every x
+        let covered_ranges =
+            Interval::merge(sensors.iter().filter_map(|s| s.row_coverage(self.row)));
+        let beacon_set = HashSet::<_>::from_iter(sensors.iter().map(|s| s.closest_beacon));
+        let beacons_in_row = beacon_set
+            .iter()
+            .filter(|&&Point { x, y }| {
+                y == self.row && covered_ranges.iter().any(|r| r.contains_value(x as i64))
+            })
+            .count();
+
+        let covered: u64 = covered_ranges.iter().map(|r| r.len()).sum();
+        Ok((covered as usize - beacons_in_row).into())
+    }
+
+    fn part2(&self, sensors: &Vec<Sensor>) -> Result<Answer, AocError> {
+        let distr_max = self.search_bound;
+        // https://github.com/saulvaldelvira/AdventOfCode/blob/5ebda5ec175e15bdb42f217767592e68ae00a829/2022/Day15/puzzle15.c#L165
+        let Point { x, y } = sensors
+            .iter()
+            .filter_map(|s| {
+                s.sensor_perimiter(0, distr_max).into_iter().find(|&c| {
+                    //  println!("({},{}) is {}", c.0, c.1, covered);
+                    !sensors.iter().any(|s| s.is_within_range(c))
+                })
+            })
+            .next()
+            .ok_or("Distress Beacon not found")?;
         let res_freq: i64 = (x as i64) * 4000000 + (y as i64);
-        println!("Part2 Result: {res_freq}");
-    } else {
-        println!("not found")
+        Ok(res_freq.into())
     }
+}
 
+#[cfg(test)]
+mod sensor_test {
+    use super::Sensor;
+    use crate::geom::Point;
+
+    fn points(ps: &[(i32, i32)]) -> Vec<Point> {
+        ps.iter().map(|&p| p.into()).collect()
+    }
+
+    #[test]
+    fn sensor_perimiter_test_range_zero() {
+        let s = Sensor::new(Point::new(0, 0), Point::new(0, 0));
 
-    Ok(())
-}
\ No newline at end of file
+        let expected_perimiter = vec![(0, -1), (1, 0), (0, 1), (-1, 0)];
+        assert_eq!(points(&expected_perimiter), s.sensor_perimiter(-2, 2));
+    }
+
+    #[test]
+    fn sensor_perimiter_test_range_one() {
+        let s = Sensor::new(Point::new(0, 0), Point::new(0, 1));
+
+        let expected_perimiter = vec![
+            (0, -2),
+            (1, -1),
+            (2, 0),
+            (1, 1),
+            (0, 2),
+            (-1, 1),
+            (-2, 0),
+            (-1, -1),
+        ];
+        assert_eq!(points(&expected_perimiter), s.sensor_perimiter(-2, 2));
+    }
+
+    #[test]
+    fn sensor_perimiter_test_clockwise() {
+        let s = Sensor::new(Point::new(3, -2), Point::new(6, 0));
+        let perimiter = s.sensor_perimiter(-20, 20);
+
+        assert_eq!(perimiter.len(), 4 * 6);
+        assert!(perimiter.iter().all(|&p| s.pos.manhattan(p) == 6));
+        // every point is a diagonal step away from the one before it, wrapping around at the end
+        let mut steps = perimiter.iter().zip(perimiter.iter().cycle().skip(1));
+        assert!(steps.all(|(&a, &b)| {
+            let d = b - a;
+            d.x.abs() == 1 && d.y.abs() == 1
+        }));
+    }
+
+    #[test]
+    fn sensor_perimiter_test_range_one_with_bounds() {
+        let s = Sensor::new(Point::new(0, 0), Point::new(0, 1));
+
+        let expected_perimiter = vec![(2, 0), (1, 1), (0, 2)];
+        assert_eq!(points(&expected_perimiter), s.sensor_perimiter(0, 2));
+    }
+}
@@ -0,0 +1,502 @@
+use std::fs;
+
+use itertools::Itertools;
+
+use crate::{
+    error::{column_of, parse_lines},
+    Answer, AocError, Solution,
+};
+
+use tournament::Opponent;
+
+mod tournament;
+
+/// The puzzle's rules, in the format [`Rules::parse`] reads.
+const CLASSIC_RULES: &str = "\
+# name score opponent-code player-code
+shape Rock 1 A X
+shape Paper 2 B Y
+shape Scissors 3 C Z
+Rock beats Scissors
+Scissors beats Paper
+Paper beats Rock";
+
+/// One of the shapes of the loaded [`Rules`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape {
+    id: usize,
+    score: i32,
+}
+
+impl Shape {
+    pub fn from_expected_result(
+        r: &GameResult,
+        opponent_move: &Shape,
+        rules: &Rules,
+    ) -> Result<Shape, AocError> {
+        let matching = rules
+            .shapes()
+            .filter(|p2| &rules.result(*opponent_move, *p2) == r)
+            .collect_vec();
+        match matching[..] {
+            [shape] => Ok(shape),
+            [] => Err(format!(
+                "no shape gives a {:?} against {}",
+                r,
+                rules.name(opponent_move)
+            )
+            .into()),
+            _ => Err(format!(
+                "ambiguous expected result, {} all give a {:?} against {}",
+                matching.iter().map(|s| rules.name(s)).join(", "),
+                r,
+                rules.name(opponent_move)
+            )
+            .into()),
+        }
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+}
+
+struct ShapeRule {
+    name: String,
+    score: i32,
+    opponent_code: String,
+    code: String,
+}
+
+/// Rock-paper-scissors like game with any number of shapes, i.e. Rock-Paper-Scissors-Lizard-Spock.
+pub struct Rules {
+    shapes: Vec<ShapeRule>,
+    // beats[a][b] is true when shape a beats shape b
+    beats: Vec<Vec<bool>>,
+}
+
+impl Rules {
+    /// One definition per line, blank lines and lines starting with `#` are skipped:
+    /// - `shape <name> <score> <opponent-code> <player-code>` adds a shape with the codes used for it in the guide
+    /// - `<name> beats <name>` decides a pair of shapes, every pair of different shapes has to be decided exactly once
+    pub fn parse(text: &str) -> Result<Rules, AocError> {
+        let mut shapes: Vec<ShapeRule> = vec![];
+        let mut wins: Vec<(usize, usize, &str, usize)> = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let on_line = |e: AocError| e.on_line(i + 1);
+            let tokens = line.split_ascii_whitespace().collect_vec();
+            let shape_id = |shapes: &[ShapeRule], name: &str| {
+                shapes.iter().position(|s| s.name == name).ok_or_else(|| {
+                    AocError::parse_token(line, name, "unknown shape").on_line(i + 1)
+                })
+            };
+            match tokens[..] {
+                [] => {}
+                [comment, ..] if comment.starts_with('#') => {}
+                ["shape", name, score, opponent_code, code] => {
+                    let score = score
+                        .parse()
+                        .map_err(|e| on_line(AocError::parse_token(line, score, format!("invalid score, {}", e))))?;
+                    let taken = shapes.iter().find_map(|s| {
+                        [(name, &s.name), (opponent_code, &s.opponent_code), (code, &s.code)]
+                            .into_iter()
+                            .find(|(new, used)| new == used)
+                            .map(|(new, _)| new)
+                    });
+                    if let Some(token) = taken {
+                        return Err(on_line(AocError::parse_token(line, token, "already used by another shape")));
+                    }
+                    shapes.push(ShapeRule {
+                        name: name.to_string(),
+                        score,
+                        opponent_code: opponent_code.to_string(),
+                        code: code.to_string(),
+                    });
+                }
+                [winner, "beats", loser] => wins.push((shape_id(&shapes, winner)?, shape_id(&shapes, loser)?, line, i + 1)),
+                _ => {
+                    return Err(on_line(AocError::parse(
+                        1,
+                        line,
+                        "expected 'shape <name> <score> <opponent-code> <player-code>' or '<name> beats <name>'",
+                    )))
+                }
+            }
+        }
+
+        let mut beats = vec![vec![false; shapes.len()]; shapes.len()];
+        for (winner, loser, line, line_no) in wins {
+            if winner == loser || beats[winner][loser] || beats[loser][winner] {
+                let message = format!(
+                    "{} and {} are already decided",
+                    shapes[winner].name, shapes[loser].name
+                );
+                return Err(AocError::parse(1, line, message).on_line(line_no));
+            }
+            beats[winner][loser] = true;
+        }
+        if shapes.is_empty() {
+            return Err("no shapes defined".into());
+        }
+        for (a, b) in (0..shapes.len()).tuple_combinations() {
+            if !beats[a][b] && !beats[b][a] {
+                return Err(format!(
+                    "neither {} nor {} beats the other",
+                    shapes[a].name, shapes[b].name
+                )
+                .into());
+            }
+        }
+        Ok(Rules { shapes, beats })
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> + '_ {
+        self.shapes
+            .iter()
+            .enumerate()
+            .map(|(id, s)| Shape { id, score: s.score })
+    }
+
+    pub fn name(&self, shape: &Shape) -> &str {
+        &self.shapes[shape.id].name
+    }
+
+    pub fn opponent_shape(&self, c: &str) -> Option<Shape> {
+        self.shapes().find(|s| self.shapes[s.id].opponent_code == c)
+    }
+
+    pub fn player_shape(&self, c: &str) -> Option<Shape> {
+        self.shapes().find(|s| self.shapes[s.id].code == c)
+    }
+
+    /// Result of the game for the `player`.
+    pub fn result(&self, opponent: Shape, player: Shape) -> GameResult {
+        if self.beats[player.id][opponent.id] {
+            GameResult::Win
+        } else if self.beats[opponent.id][player.id] {
+            GameResult::Lose
+        } else {
+            GameResult::Draw
+        }
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::parse(CLASSIC_RULES).expect("classic rules are valid")
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum GameResult {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl GameResult {
+    pub fn score(&self) -> i32 {
+        match self {
+            GameResult::Lose => 0,
+            GameResult::Draw => 3,
+            GameResult::Win => 6,
+        }
+    }
+
+    pub fn from_secret_code(c: &str) -> Result<GameResult, &str> {
+        match c {
+            "X" => Ok(GameResult::Lose),
+            "Y" => Ok(GameResult::Draw),
+            "Z" => Ok(GameResult::Win),
+            _ => Err("invalid secret result code, only X, Y and Z are supported!"),
+        }
+    }
+}
+
+/// One line of the strategy guide, the opponent's shape and the still undecoded secret code.
+pub struct GuideEntry {
+    opponent: Shape,
+    secret_code: String,
+    // 1 based column of the secret code, for errors found while decoding it
+    secret_column: usize,
+}
+
+impl GuideEntry {
+    pub fn from_input(line: &str, rules: &Rules) -> Result<GuideEntry, AocError> {
+        let parts: Vec<&str> = line.split_ascii_whitespace().collect();
+        match parts[..] {
+            [left, right] => {
+                let opponent = rules.opponent_shape(left).ok_or_else(|| {
+                    AocError::parse_token(
+                        line,
+                        left,
+                        "invalid plain code, not an opponent code of the rules",
+                    )
+                })?;
+                // the code is decoded later, either as a shape or as an expected result
+                if rules.player_shape(right).is_none()
+                    && GameResult::from_secret_code(right).is_err()
+                {
+                    return Err(AocError::parse_token(
+                        line,
+                        right,
+                        "invalid secret code, neither a shape code nor X, Y or Z",
+                    ));
+                }
+                Ok(GuideEntry {
+                    opponent,
+                    secret_code: right.to_string(),
+                    secret_column: column_of(line, right),
+                })
+            }
+            _ => Err(AocError::parse(
+                1,
+                line,
+                "Invalid inputs found, expected 2 whitespace separated codes",
+            )),
+        }
+    }
+
+    // decoding error pointing at the secret code, the caller adds the line
+    fn error(&self, message: impl Into<String>) -> AocError {
+        AocError::parse(self.secret_column, &self.secret_code, message)
+    }
+}
+
+pub struct Game {
+    player_1: Shape,
+    player_2: Shape,
+}
+
+impl Game {
+    pub fn from_shape_code(entry: &GuideEntry, rules: &Rules) -> Result<Game, AocError> {
+        let r_hand = rules
+            .player_shape(&entry.secret_code)
+            .ok_or_else(|| entry.error("invalid secret shape code"))?;
+        Ok(Game {
+            player_1: entry.opponent,
+            player_2: r_hand,
+        })
+    }
+
+    pub fn from_result_code(entry: &GuideEntry, rules: &Rules) -> Result<Game, AocError> {
+        let expected_result =
+            GameResult::from_secret_code(&entry.secret_code).map_err(|e| entry.error(e))?;
+        let r_hand = Shape::from_expected_result(&expected_result, &entry.opponent, rules)
+            .map_err(|e| entry.error(e.to_string()))?;
+        Ok(Game {
+            player_1: entry.opponent,
+            player_2: r_hand,
+        })
+    }
+
+    pub fn player_2_result(&self, rules: &Rules) -> GameResult {
+        rules.result(self.player_1, self.player_2)
+    }
+
+    pub fn player_2_score(&self, rules: &Rules) -> i32 {
+        self.player_2_result(rules).score() + self.player_2.score()
+    }
+}
+
+/// How the second column of the strategy guide is read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// X, Y and Z is the shape to play.
+    Shape,
+    /// X, Y and Z is the result the game should end with.
+    Outcome,
+}
+
+impl Strategy {
+    fn from_option(option: &str, value: &str) -> Result<Strategy, AocError> {
+        match value {
+            "shape" => Ok(Strategy::Shape),
+            "outcome" => Ok(Strategy::Outcome),
+            _ => Err(AocError::invalid_option(
+                option,
+                "expected shape or outcome",
+            )),
+        }
+    }
+
+    fn decode(&self, entry: &GuideEntry, rules: &Rules) -> Result<Game, AocError> {
+        match self {
+            Strategy::Shape => Game::from_shape_code(entry, rules),
+            Strategy::Outcome => Game::from_result_code(entry, rules),
+        }
+    }
+}
+
+fn total_score(guide: &[GuideEntry], strategy: Strategy, rules: &Rules) -> Result<i32, AocError> {
+    // every guide line is an entry, so entry i comes from line i + 1
+    let games: Vec<Game> = guide
+        .iter()
+        .enumerate()
+        .map(|(i, e)| strategy.decode(e, rules).map_err(|err| err.on_line(i + 1)))
+        .collect::<Result<_, AocError>>()?;
+    Ok(games.iter().map(|g| g.player_2_score(rules)).sum())
+}
+
+/// What part 1 reports, part 2 always is the guide's total score.
+#[derive(Debug, Clone, PartialEq)]
+enum Report {
+    Score,
+    /// The substitution of codes to shapes scoring the most.
+    Optimize,
+    /// The guide replayed against another opponent.
+    Simulate,
+}
+
+/// Both parts score the same guide, each reading it with its own strategy.
+pub struct Day2 {
+    part1: Strategy,
+    part2: Strategy,
+    rules: Rules,
+    report: Report,
+    opponent: Opponent,
+}
+
+impl Default for Day2 {
+    fn default() -> Self {
+        Day2 {
+            part1: Strategy::Shape,
+            part2: Strategy::Outcome,
+            rules: Rules::default(),
+            report: Report::Score,
+            opponent: Opponent::Adaptive,
+        }
+    }
+}
+
+impl Solution for Day2 {
+    type Input = Vec<GuideEntry>;
+
+    fn parse(&self, input: &str) -> Result<Vec<GuideEntry>, AocError> {
+        parse_lines(input, |l| GuideEntry::from_input(l, &self.rules))
+    }
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), AocError> {
+        match option {
+            "part1" => self.part1 = Strategy::from_option(option, value)?,
+            "part2" => self.part2 = Strategy::from_option(option, value)?,
+            "rules" => {
+                let text = fs::read_to_string(value).map_err(|e| {
+                    AocError::invalid_option(option, format!("failed to read {}: {}", value, e))
+                })?;
+                self.rules = Rules::parse(&text)
+                    .map_err(|e| AocError::invalid_option(option, format!("{}: {}", value, e)))?;
+            }
+            "report" => {
+                self.report = match value {
+                    "score" => Report::Score,
+                    "optimize" => Report::Optimize,
+                    "simulate" => Report::Simulate,
+                    _ => {
+                        return Err(AocError::invalid_option(
+                            option,
+                            "expected score, optimize or simulate",
+                        ))
+                    }
+                }
+            }
+            "opponent" => {
+                self.opponent =
+                    Opponent::parse(value).map_err(|e| AocError::invalid_option(option, e))?
+            }
+            _ => {
+                return Err(AocError::invalid_option(
+                    option,
+                    "unknown option, day2 supports [part1, part2, rules, report, opponent]",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn part1(&self, guide: &Vec<GuideEntry>) -> Result<Answer, AocError> {
+        match self.report {
+            Report::Score => Ok(total_score(guide, self.part1, &self.rules)?.into()),
+            Report::Optimize => Ok(Answer::Multiline(
+                tournament::optimize(guide, &self.rules)?.to_string(),
+            )),
+            Report::Simulate => {
+                let tournament = tournament::simulate(guide, &self.opponent, &self.rules)?;
+                Ok(Answer::Multiline(tournament.to_string()))
+            }
+        }
+    }
+
+    fn part2(&self, guide: &Vec<GuideEntry>) -> Result<Answer, AocError> {
+        Ok(total_score(guide, self.part2, &self.rules)?.into())
+    }
+}
+// DAY 2
+
+#[cfg(test)]
+mod day2_test {
+    use super::*;
+
+    const LIZARD_SPOCK: &str = "\
+shape Rock 1 A V
+shape Paper 2 B W
+shape Scissors 3 C X
+shape Lizard 4 D Y
+shape Spock 5 E Z
+Scissors beats Paper
+Paper beats Rock
+Rock beats Lizard
+Lizard beats Spock
+Spock beats Scissors
+Scissors beats Lizard
+Lizard beats Paper
+Paper beats Spock
+Spock beats Rock
+Rock beats Scissors";
+
+    #[test]
+    fn rules_test() {
+        let rules = Rules::parse(LIZARD_SPOCK).unwrap();
+        let guide = parse_lines("D Y\nE Z\nA V", |l| GuideEntry::from_input(l, &rules)).unwrap();
+        // Lizard against Lizard, Spock against Spock and Rock against Rock are all draws
+        assert_eq!(
+            total_score(&guide, Strategy::Shape, &rules).unwrap(),
+            4 + 5 + 1 + 3 * 3
+        );
+        // a draw against Lizard is unambiguous, a win against Spock isn't
+        assert_eq!(
+            total_score(&guide, Strategy::Outcome, &rules).unwrap_err().to_string(),
+            "parse error at line 2, column 3: ambiguous expected result, Paper, Lizard all give a Win against Spock, got: 'Z'"
+        );
+        let guide = parse_lines("A V\nC X", |l| GuideEntry::from_input(l, &rules)).unwrap();
+        assert_eq!(
+            total_score(&guide, Strategy::Outcome, &rules).unwrap_err().to_string(),
+            "parse error at line 1, column 3: invalid secret result code, only X, Y and Z are supported!, got: 'V'"
+        );
+    }
+
+    #[test]
+    fn invalid_rules_test() {
+        assert_eq!(
+            Rules::parse("shape Rock 1 A X\nshape Paper 2 B X")
+                .err()
+                .unwrap()
+                .to_string(),
+            "parse error at line 2, column 17: already used by another shape, got: 'X'"
+        );
+        assert_eq!(
+            Rules::parse("shape Rock 1 A X\nRock beats Paper")
+                .err()
+                .unwrap()
+                .to_string(),
+            "parse error at line 2, column 12: unknown shape, got: 'Paper'"
+        );
+        assert_eq!(
+            Rules::parse("shape Rock 1 A X\nshape Paper 2 B Y")
+                .err()
+                .unwrap()
+                .to_string(),
+            "neither Rock nor Paper beats the other"
+        );
+    }
+}
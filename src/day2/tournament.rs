@@ -0,0 +1,284 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::day2::{Game, GameResult, GuideEntry, Rules, Shape};
+
+/// Best way to read the guide's second column as shapes, found by trying every substitution of codes to shapes.
+pub struct Substitution {
+    shapes: Vec<(String, String)>,
+    total: i32,
+    as_written: Option<i32>,
+}
+
+impl fmt::Display for Substitution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shapes = self
+            .shapes
+            .iter()
+            .map(|(code, shape)| format!("{}={}", code, shape))
+            .join(", ");
+        writeln!(f, "best substitution: {}", shapes)?;
+        writeln!(f, "total score: {}", self.total)?;
+        match self.as_written {
+            Some(total) => writeln!(f, "guide as written: {}", total),
+            None => writeln!(f, "guide as written: not all codes are shapes"),
+        }
+    }
+}
+
+fn substitution_score(guide: &[GuideEntry], shapes: &HashMap<&str, Shape>, rules: &Rules) -> i32 {
+    guide
+        .iter()
+        .map(|e| {
+            let game = Game {
+                player_1: e.opponent,
+                player_2: shapes[e.secret_code.as_str()],
+            };
+            game.player_2_score(rules)
+        })
+        .sum()
+}
+
+/// Tries every assignment of the guide's distinct codes to different shapes, the first best one wins ties.
+pub fn optimize(guide: &[GuideEntry], rules: &Rules) -> Result<Substitution, String> {
+    if guide.is_empty() {
+        return Err("empty guide, nothing to optimize".to_string());
+    }
+    let codes = guide
+        .iter()
+        .map(|e| e.secret_code.as_str())
+        .unique()
+        .sorted()
+        .collect_vec();
+    let shapes = rules.shapes().collect_vec();
+    if codes.len() > shapes.len() {
+        return Err(format!(
+            "{} different codes in the guide, but the rules only have {} shapes",
+            codes.len(),
+            shapes.len()
+        ));
+    }
+
+    let (best, total) = shapes
+        .into_iter()
+        .permutations(codes.len())
+        .map(|assigned| {
+            let substitution: HashMap<&str, Shape> = codes.iter().cloned().zip(assigned).collect();
+            let total = substitution_score(guide, &substitution, rules);
+            (substitution, total)
+        })
+        // keeps the first of equally good substitutions, max_by_key would keep the last one
+        .fold(
+            None,
+            |best: Option<(HashMap<&str, Shape>, i32)>, (s, total)| match best {
+                Some((_, best_total)) if best_total >= total => best,
+                _ => Some((s, total)),
+            },
+        )
+        // there are at least as many shapes as codes, so there is at least one substitution
+        .expect("a non empty guide has a substitution");
+
+    let as_written: Option<HashMap<&str, Shape>> = codes
+        .iter()
+        .map(|&c| rules.player_shape(c).map(|s| (c, s)))
+        .collect();
+    Ok(Substitution {
+        shapes: codes
+            .iter()
+            .map(|&c| (c.to_string(), rules.name(&best[c]).to_string()))
+            .collect(),
+        total,
+        as_written: as_written.map(|s| substitution_score(guide, &s, rules)),
+    })
+}
+
+/// How the opponent picks shapes when a guide is replayed, instead of the moves written in the guide.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Opponent {
+    /// Always the shape with this name.
+    Fixed(String),
+    /// Uniformly random shapes, the same seed always plays the same moves.
+    Random(u64),
+    /// Plays whatever beats the shape the player used most often so far.
+    Adaptive,
+}
+
+impl Opponent {
+    /// `fixed:<shape>`, `random:<seed>` or `adaptive`.
+    pub fn parse(s: &str) -> Result<Opponent, String> {
+        match s.split_once(':') {
+            Some(("fixed", shape)) => Ok(Opponent::Fixed(shape.to_string())),
+            Some(("random", seed)) => seed
+                .parse()
+                .map(Opponent::Random)
+                .map_err(|e| format!("invalid seed: {}, {}", seed, e)),
+            None if s == "adaptive" => Ok(Opponent::Adaptive),
+            _ => Err("expected fixed:<shape>, random:<seed> or adaptive".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Opponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Opponent::Fixed(shape) => write!(f, "fixed:{}", shape),
+            Opponent::Random(seed) => write!(f, "random:{}", seed),
+            Opponent::Adaptive => write!(f, "adaptive"),
+        }
+    }
+}
+
+// xorshift64, plenty random for picking shapes and reproducible without extra dependencies
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> XorShift {
+        // the state must never be 0
+        XorShift(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+pub struct Tournament {
+    opponent: Opponent,
+    wins: usize,
+    draws: usize,
+    losses: usize,
+    total: i32,
+}
+
+impl fmt::Display for Tournament {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "opponent: {}", self.opponent)?;
+        writeln!(
+            f,
+            "games: {}, wins: {}, draws: {}, losses: {}",
+            self.wins + self.draws + self.losses,
+            self.wins,
+            self.draws,
+            self.losses
+        )?;
+        writeln!(f, "total score: {}", self.total)
+    }
+}
+
+/// Replays the guide's second column, read as shapes, against moves picked by `opponent`.
+pub fn simulate(
+    guide: &[GuideEntry],
+    opponent: &Opponent,
+    rules: &Rules,
+) -> Result<Tournament, String> {
+    let shapes = rules.shapes().collect_vec();
+    let fixed = match opponent {
+        Opponent::Fixed(name) => Some(
+            shapes
+                .iter()
+                .find(|s| rules.name(s) == name)
+                .ok_or(format!("unknown shape: {}", name))?,
+        ),
+        _ => None,
+    };
+    let mut rng = match opponent {
+        Opponent::Random(seed) => XorShift::new(*seed),
+        _ => XorShift::new(0),
+    };
+    // how often the player used each shape so far
+    let mut played = vec![0; shapes.len()];
+
+    let mut tournament = Tournament {
+        opponent: opponent.clone(),
+        wins: 0,
+        draws: 0,
+        losses: 0,
+        total: 0,
+    };
+    for entry in guide {
+        let player_2 = rules
+            .player_shape(&entry.secret_code)
+            .ok_or(format!("invalid secret shape code: {}", entry.secret_code))?;
+        let player_1 = match (fixed, opponent) {
+            (Some(&shape), _) => shape,
+            (_, Opponent::Random(_)) => shapes[rng.below(shapes.len())],
+            _ => {
+                // max_by_key keeps the last maximum, scanning backwards makes ties go to the first shape
+                let expected = shapes[(0..shapes.len())
+                    .rev()
+                    .max_by_key(|&i| played[i])
+                    .unwrap_or(0)];
+                // with a single shape nothing beats it, so draw against it
+                shapes
+                    .iter()
+                    .find(|&&s| rules.result(expected, s) == GameResult::Win)
+                    .copied()
+                    .unwrap_or(expected)
+            }
+        };
+        played[player_2.id] += 1;
+
+        let game = Game { player_1, player_2 };
+        match game.player_2_result(rules) {
+            GameResult::Win => tournament.wins += 1,
+            GameResult::Draw => tournament.draws += 1,
+            GameResult::Lose => tournament.losses += 1,
+        }
+        tournament.total += game.player_2_score(rules);
+    }
+    Ok(tournament)
+}
+
+#[cfg(test)]
+mod tournament_test {
+    use super::*;
+    use crate::error::parse_lines;
+
+    fn guide(text: &str, rules: &Rules) -> Vec<GuideEntry> {
+        parse_lines(text, |l| GuideEntry::from_input(l, rules)).unwrap()
+    }
+
+    #[test]
+    fn optimize_test() {
+        let rules = Rules::default();
+        // every game of the example is won by reading Y as Paper, X as Scissors and Z as Rock
+        let example = guide("A Y\nB X\nC Z", &rules);
+        let best = optimize(&example, &rules).unwrap();
+        assert_eq!(
+            best.to_string(),
+            "best substitution: X=Scissors, Y=Paper, Z=Rock\ntotal score: 24\nguide as written: 15\n"
+        );
+        assert_eq!(
+            optimize(&[], &rules).err(),
+            Some("empty guide, nothing to optimize".to_string())
+        );
+    }
+
+    #[test]
+    fn simulate_test() {
+        let rules = Rules::default();
+        let all_rock = guide("A X\nA X\nA X", &rules);
+
+        let fixed = simulate(&all_rock, &Opponent::Fixed("Paper".to_string()), &rules).unwrap();
+        assert_eq!((fixed.losses, fixed.total), (3, 3));
+        // the adaptive opponent expects Rock before it has seen anything, and keeps seeing Rock
+        let adaptive = simulate(&all_rock, &Opponent::Adaptive, &rules).unwrap();
+        assert_eq!((adaptive.wins, adaptive.draws, adaptive.losses), (0, 0, 3));
+
+        let random = simulate(&all_rock, &Opponent::Random(7), &rules).unwrap();
+        assert_eq!(
+            random.to_string(),
+            simulate(&all_rock, &Opponent::Random(7), &rules)
+                .unwrap()
+                .to_string()
+        );
+        assert!(simulate(&all_rock, &Opponent::Fixed("Spock".to_string()), &rules).is_err());
+        assert_eq!(Opponent::parse("random:42"), Ok(Opponent::Random(42)));
+        assert!(Opponent::parse("smart").is_err());
+    }
+}
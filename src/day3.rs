@@ -1,77 +1,307 @@
-use std::{collections::HashSet, error::Error, process};
+use core::fmt;
+use std::ops::{BitAnd, BitOr, BitXor};
+
+use itertools::Itertools;
+
+use crate::{error::parse_lines, Answer, AocError, Solution};
 
 // DAY 3
+/// Set of item types, one bit per letter: bit 0 is `a`, bit 25 `z`, bit 26 `A` and bit 51 `Z`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+
+    /// Priority of an item, 1 to 26 for `a` to `z` and 27 to 52 for `A` to `Z`, None for anything but ASCII letters.
+    pub fn priority(item: char) -> Option<u32> {
+        match item {
+            'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+            'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+            _ => None,
+        }
+    }
+
+    /// Items of `text`, the error is the position and char of the first item which isn't a letter.
+    pub fn parse(text: &str) -> Result<ItemSet, (usize, char)> {
+        text.chars()
+            .enumerate()
+            .try_fold(ItemSet::EMPTY, |set, (i, c)| {
+                let priority = ItemSet::priority(c).ok_or((i, c))?;
+                Ok(ItemSet(set.0 | 1 << (priority - 1)))
+            })
+    }
+
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Items in the set, in priority order.
+    pub fn items(&self) -> impl Iterator<Item = char> + '_ {
+        ('a'..='z')
+            .chain('A'..='Z')
+            .enumerate()
+            .filter(|&(bit, _)| self.0 & 1 << bit != 0)
+            .map(|(_, c)| c)
+    }
+
+    pub fn priority_sum(&self) -> u32 {
+        (0..52)
+            .filter(|bit| self.0 & 1 << bit != 0)
+            .map(|bit| bit + 1)
+            .sum()
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+}
+
+impl BitXor for ItemSet {
+    type Output = ItemSet;
+
+    fn bitxor(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 ^ other.0)
+    }
+}
+
+impl fmt::Display for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.items().join(", "))
+    }
+}
+
 #[derive(Debug)]
 pub struct Rucksack {
-    compartment_a: Vec<char>,
-    compartment_b: Vec<char>,
+    compartment_a: ItemSet,
+    compartment_b: ItemSet,
 }
 
 impl Rucksack {
-    pub fn from_input(l: &String) -> Result<Rucksack, &str> {
-        if l.chars().count() % 2 != 0 {
-            return Err("Invalid input, only even character counts are supported");
+    pub fn from_input(l: &str) -> Result<Rucksack, AocError> {
+        let half = l.chars().count() / 2;
+        if !l.chars().count().is_multiple_of(2) {
+            return Err(AocError::parse(
+                1,
+                l,
+                "Invalid input, only even character counts are supported",
+            ));
         }
-        let (a, b) = l.split_at(l.chars().count() / 2);
+        let (a, b) = l.split_at(l.char_indices().nth(half).map_or(l.len(), |(i, _)| i));
+        let items = |compartment, offset| {
+            ItemSet::parse(compartment).map_err(|(i, c): (usize, char)| {
+                AocError::parse(
+                    offset + i + 1,
+                    &c.to_string(),
+                    "invalid item, only letters a-z and A-Z are supported",
+                )
+            })
+        };
         Ok(Rucksack {
-            compartment_a: a.chars().collect(),
-            compartment_b: b.chars().collect(),
+            compartment_a: items(a, 0)?,
+            compartment_b: items(b, half)?,
         })
     }
 
-    pub fn contents(&self) -> Vec<&char> {
-        self.compartment_a
-            .iter()
-            .chain(self.compartment_b.iter())
-            .collect()
+    pub fn contents(&self) -> ItemSet {
+        self.compartment_a | self.compartment_b
     }
 
-    pub fn item_priority(i: &char) -> u32 {
-        // println!("i: {}",i);
-        let ascii = *i as u32;
-        if i.is_uppercase() {
-            ascii - 38 // - 65 + 26
-        } else {
-            ascii - 96
-        }
+    pub fn compartment_overlaps(&self) -> ItemSet {
+        self.compartment_a & self.compartment_b
     }
 }
 
-fn intersection<'a>(contents_a: Vec<&'a char>, contents_b: Vec<&'a char>) -> Vec<&'a char> {
-    let self_invent = HashSet::<_>::from_iter(contents_a.iter().map(|i| *i));
-    let other_invent = HashSet::<_>::from_iter(contents_b.iter().map(|i| *i));
-    self_invent
-        .intersection(&other_invent)
-        .map(|c| *c)
+/// Badge of every group of `group_size` consecutive rucksacks, the one item all of them carry.
+fn group_badges(rucksacks: &[Rucksack], group_size: usize) -> Result<Vec<ItemSet>, AocError> {
+    rucksacks
+        .chunks(group_size)
+        .enumerate()
+        .map(|(i, g)| {
+            let lines = format!("lines {}-{}", i * group_size + 1, i * group_size + g.len());
+            if g.len() != group_size {
+                return Err(format!(
+                    "{}: incomplete group of {} rucksacks, expected {}",
+                    lines,
+                    g.len(),
+                    group_size
+                )
+                .into());
+            }
+            let group_overlaps = g
+                .iter()
+                .map(|r| r.contents())
+                .reduce(|acc, items| acc & items)
+                .expect("chunks are never empty");
+            match group_overlaps.count() {
+                1 => Ok(group_overlaps),
+                0 => Err(format!("{}: no item is shared by the whole group", lines).into()),
+                _ => Err(format!(
+                    "{}: expected a single badge, the whole group shares {}",
+                    lines, group_overlaps
+                )
+                .into()),
+            }
+        })
         .collect()
 }
 
-pub fn result(lines: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let rucksacks: Result<Vec<Rucksack>, &str> = lines.iter().map(Rucksack::from_input).collect();
-    match rucksacks {
-        Ok(rucksacks) => {
-            // let misplaced_items: u32 = rucksacks.iter()
-            //     .flat_map(|r| r.compartment_overlaps())
-            //     .map(Rucksack::item_priority)
-            //     .sum();
-            // let groups =        rucksacks.iter().next_chunk::<3>().expect("invalid input - number of lines need to be divisable by 3");
-            let group_badges: u32 = rucksacks.chunks(3).map(|g| {
-                    let group_overlaps = g.iter().map(|r|r.contents()).reduce(intersection).expect("Invalid input - empty elf group provided, expected 3 elves in each group.");
-
-                    if group_overlaps.len() != 1 {
-                        eprintln!("error processing input, only single badge should overlap in each group, got: {}", group_overlaps.len());
-                        process::exit(1)
-                    }
-                    group_overlaps[0]
-                }).map(|i| Rucksack::item_priority(i)).sum();
-
-            println!("result is {}", group_badges);
-        }
-        Err(e) => {
-            eprintln!("error processing input: {}", e);
+pub struct Day3 {
+    group_size: usize,
+}
+
+impl Default for Day3 {
+    fn default() -> Self {
+        Day3 { group_size: 3 }
+    }
+}
+
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Rucksack>, AocError> {
+        parse_lines(input, Rucksack::from_input)
+    }
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), AocError> {
+        match option {
+            "group_size" => {
+                self.group_size =
+                    value.parse().ok().filter(|&size| size > 0).ok_or_else(|| {
+                        AocError::invalid_option(option, "expected a positive number")
+                    })?
+            }
+            _ => {
+                return Err(AocError::invalid_option(
+                    option,
+                    "unknown option, day3 supports [group_size]",
+                ))
+            }
         }
+        Ok(())
+    }
+
+    fn part1(&self, rucksacks: &Vec<Rucksack>) -> Result<Answer, AocError> {
+        let misplaced_items: u32 = rucksacks
+            .iter()
+            .map(|r| r.compartment_overlaps().priority_sum())
+            .sum();
+        Ok(misplaced_items.into())
+    }
+
+    fn part2(&self, rucksacks: &Vec<Rucksack>) -> Result<Answer, AocError> {
+        let group_badges: u32 = group_badges(rucksacks, self.group_size)?
+            .into_iter()
+            .map(|badge| badge.priority_sum())
+            .sum();
+        Ok(group_badges.into())
     }
-    Ok(())
 }
 
 // DAY 3 END
+
+#[cfg(test)]
+mod day3_test {
+    use std::{collections::HashSet, time::Instant};
+
+    use super::*;
+
+    #[test]
+    fn item_set_test() {
+        let a = ItemSet::parse("abcZ").unwrap();
+        let b = ItemSet::parse("bZq").unwrap();
+        assert_eq!((a & b).to_string(), "b, Z");
+        assert_eq!((a | b).count(), 5);
+        assert_eq!((a ^ b).to_string(), "a, c, q");
+        assert_eq!((a & b).priority_sum(), 2 + 52);
+        assert_eq!(ItemSet::parse("ab1"), Err((2, '1')));
+        assert_eq!(
+            parse_lines("abcd-f", Rucksack::from_input).unwrap_err().to_string(),
+            "parse error at line 1, column 5: invalid item, only letters a-z and A-Z are supported, got: '-'"
+        );
+    }
+
+    // compares with the HashSet intersection ItemSet replaced, run with
+    // cargo test --release item_set_bench -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn item_set_bench() {
+        let letters = ('a'..='z').chain('A'..='Z').collect_vec();
+        let mut state = 0x2545_F491_4F6C_DD1D_u64;
+        let mut random_letter = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            letters[(state % 52) as usize]
+        };
+        let lines = (0..500_000)
+            .map(|_| (0..48).map(|_| random_letter()).collect::<String>())
+            .collect_vec();
+
+        let start = Instant::now();
+        let hash_set_overlaps: usize = lines
+            .iter()
+            .map(|l| {
+                let (a, b) = l.split_at(l.len() / 2);
+                let a: HashSet<char> = a.chars().collect();
+                let b: HashSet<char> = b.chars().collect();
+                a.intersection(&b).count()
+            })
+            .sum();
+        let hash_set_time = start.elapsed();
+
+        let start = Instant::now();
+        let item_set_overlaps: u32 = lines
+            .iter()
+            .map(|l| {
+                Rucksack::from_input(l)
+                    .unwrap()
+                    .compartment_overlaps()
+                    .count()
+            })
+            .sum();
+        let item_set_time = start.elapsed();
+
+        println!(
+            "{} rucksacks, HashSet: {:?}, ItemSet: {:?}",
+            lines.len(),
+            hash_set_time,
+            item_set_time
+        );
+        assert_eq!(hash_set_overlaps, item_set_overlaps as usize);
+        assert!(item_set_time < hash_set_time);
+    }
+
+    #[test]
+    fn group_badges_test() {
+        let rucksacks = parse_lines("abcA\nAdef\nAghi\nxyzq", Rucksack::from_input).unwrap();
+        assert_eq!(
+            group_badges(&rucksacks[..3], 3).unwrap(),
+            vec![ItemSet::parse("A").unwrap()]
+        );
+        assert_eq!(
+            group_badges(&rucksacks, 3).unwrap_err().to_string(),
+            "lines 4-4: incomplete group of 1 rucksacks, expected 3"
+        );
+        assert_eq!(
+            group_badges(&rucksacks, 2).unwrap_err().to_string(),
+            "lines 3-4: no item is shared by the whole group"
+        );
+        assert_eq!(
+            group_badges(&rucksacks, 1).unwrap_err().to_string(),
+            "lines 1-1: expected a single badge, the whole group shares a, b, c, A"
+        );
+    }
+}
@@ -1,85 +1,305 @@
-use std::{error::Error, num::ParseIntError};
+use core::fmt;
+use itertools::Itertools;
+
+use crate::{error::parse_lines, interval::Interval, Answer, AocError, Solution};
 
 // DAY 4
+fn parse_section(i: &str) -> Result<Interval, AocError> {
+    let edges: Result<Vec<i64>, AocError> = i
+        .split('-')
+        .map(|e| {
+            e.parse::<u32>()
+                .map(i64::from)
+                .map_err(|err| AocError::parse_token(i, e, format!("invalid section id, {}", err)))
+        })
+        .collect();
+    match edges?[..] {
+        [start, end] if start <= end => Ok(Interval::new(start, end)),
+        [_, _] => Err(AocError::parse(
+            1,
+            i,
+            "Invalid input, section range ends before it starts",
+        )),
+        _ => Err(AocError::parse(
+            1,
+            i,
+            "Invalid input, expected section range separated by '-'",
+        )),
+    }
+}
+
 #[derive(Debug)]
-struct Section {
-    start: u32,
-    end: u32,
+pub struct CleaningAssignment {
+    left: Interval,
+    right: Interval,
 }
 
-impl Section {
-    fn from_input(i: &str) -> Result<Section, Box<dyn Error>> {
-        let edges: Result<Vec<u32>, ParseIntError> =
-            i.split("-").map(|e| e.parse::<u32>()).collect();
-        let edges = edges?;
-        if edges.len() != 2 {
-            Err(format!(
-                "Invalid input, expected 2 part assigment separated by ',', got: {}",
-                i
-            )
-            .into())
+impl CleaningAssignment {
+    fn from_input(l: &str) -> Result<CleaningAssignment, AocError> {
+        let parts: Vec<&str> = l.split(',').collect();
+        if parts.len() != 2 {
+            Err(AocError::parse(
+                1,
+                l,
+                "Invalid input, expected 2 part assigment separated by ','",
+            ))
         } else {
-            Ok(Section {
-                start: edges[0],
-                end: edges[1],
-            })
+            let section = |part| parse_section(part).map_err(|e| e.within(l, part));
+            let l = section(parts[0])?;
+            let r = section(parts[1])?;
+            Ok(CleaningAssignment { left: l, right: r })
         }
     }
 
-    fn left_overlap(&self, another: &Section) -> bool {
-        another.end >= self.start && another.start <= self.end
+    fn pair_contained(&self) -> bool {
+        self.left.contains(&self.right) || self.right.contains(&self.left)
     }
 
-    fn overlap(&self, another: &Section) -> bool {
-        // left overlap
-        // ...456...  4-6
-        // .234.....  2-4
-        self.left_overlap(another) ||
-        //right overlap
-        // .234.....  2-4
-        // ...456...  4-6
-         another.left_overlap(self)
+    fn pair_overlap(&self) -> Option<Interval> {
+        self.left.intersection(&self.right)
     }
 }
 
-#[derive(Debug)]
-struct CleaningAssignment {
-    left: Section,
-    right: Section,
+/// One line per overlapping pair, with the sections both elves clean.
+fn overlap_report(assignments: &[CleaningAssignment]) -> String {
+    assignments
+        .iter()
+        .enumerate()
+        .filter_map(|(i, a)| {
+            let overlap = a.pair_overlap()?;
+            let contained = if a.pair_contained() {
+                ", fully contained"
+            } else {
+                ""
+            };
+            Some(format!(
+                "line {}: {},{} overlap in {} ({} sections{})",
+                i + 1,
+                a.left,
+                a.right,
+                overlap,
+                overlap.len(),
+                contained
+            ))
+        })
+        .join("\n")
 }
 
-impl CleaningAssignment {
-    fn from_input(l: &String) -> Result<CleaningAssignment, Box<dyn Error>> {
-        let parts: Vec<&str> = l.split(",").collect();
-        if parts.len() != 2 {
-            Err(format!(
-                "Invalid input, expected 2 part assigment separated by ',', got: {}",
-                l
-            )
-            .into())
-        } else {
-            let l = Section::from_input(parts[0])?;
-            let r = Section::from_input(parts[1])?;
-            Ok(CleaningAssignment { left: l, right: r })
+/// Sections grouped by how many elves clean them, across every assignment.
+struct Coverage {
+    span: Option<Interval>,
+    nobody: Vec<Interval>,
+    exactly_one: Vec<Interval>,
+    more_than_k: Vec<Interval>,
+    k: usize,
+}
+
+impl Coverage {
+    /// Sweeps over the start and end of every section, so the cost depends on the number of sections, not on their ids.
+    fn new(sections: impl Iterator<Item = Interval>, k: usize) -> Coverage {
+        // +1 where a section starts and -1 right after it ends
+        let mut events: Vec<(i64, i64)> = sections
+            .flat_map(|s| [(s.start, 1), (s.end + 1, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let mut coverage = Coverage {
+            span: events
+                .first()
+                .zip(events.last())
+                .map(|(&(start, _), &(end, _))| Interval::new(start, end - 1)),
+            nobody: vec![],
+            exactly_one: vec![],
+            more_than_k: vec![],
+            k,
+        };
+        let mut elves = 0;
+        for (&(pos, delta), &(next, _)) in events.iter().tuple_windows() {
+            elves += delta;
+            if pos == next {
+                continue;
+            }
+            // every section from pos up to the next event is cleaned by the same number of elves
+            let covered = Interval::new(pos, next - 1);
+            match elves {
+                0 => push_merged(&mut coverage.nobody, covered),
+                1 => push_merged(&mut coverage.exactly_one, covered),
+                _ => {}
+            }
+            if elves > k as i64 {
+                push_merged(&mut coverage.more_than_k, covered);
+            }
         }
+        coverage
     }
+}
 
-    fn pair_overlap(&self) -> bool {
-        self.left.overlap(&self.right)
+// ranges come in sorted, so a range touching the previous one extends it
+fn push_merged(ranges: &mut Vec<Interval>, range: Interval) {
+    match ranges.last_mut() {
+        Some(last) if last.end + 1 == range.start => last.end = range.end,
+        _ => ranges.push(range),
     }
 }
 
-pub fn result(lines: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let overlapping: Result<Vec<CleaningAssignment>, Box<dyn Error>> =
-        lines.iter().map(CleaningAssignment::from_input).collect();
-    let overlapping_count = overlapping?
-        .iter()
-        .filter(|a| a.pair_overlap())
-        .map(|i| i)
-        .count();
-    println!("Result is: {}", overlapping_count);
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(span) = self.span else {
+            return writeln!(f, "no sections assigned");
+        };
+        writeln!(f, "span: {} ({} sections)", span, span.len())?;
+        let groups = [
+            ("nobody".to_string(), &self.nobody),
+            ("exactly one elf".to_string(), &self.exactly_one),
+            (format!("more than {} elves", self.k), &self.more_than_k),
+        ];
+        for (name, ranges) in groups {
+            let sections: u64 = ranges.iter().map(|r| r.len()).sum();
+            // millions of assignments can split coverage into as many ranges, only the first ones are listed
+            let mut listed = ranges.iter().take(10).join(", ");
+            if ranges.len() > 10 {
+                listed.push_str(&format!(", ... {} more", ranges.len() - 10));
+            }
+            writeln!(
+                f,
+                "covered by {}: {} sections in {} ranges: {}",
+                name,
+                sections,
+                ranges.len(),
+                listed
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Report {
+    Count,
+    Overlaps,
+    Coverage,
+}
 
-    Ok(())
+pub struct Day4 {
+    report: Report,
+    k: usize,
+}
+
+impl Default for Day4 {
+    fn default() -> Self {
+        Day4 {
+            report: Report::Count,
+            k: 2,
+        }
+    }
+}
+
+impl Solution for Day4 {
+    type Input = Vec<CleaningAssignment>;
+
+    fn parse(&self, input: &str) -> Result<Vec<CleaningAssignment>, AocError> {
+        parse_lines(input, CleaningAssignment::from_input)
+    }
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), AocError> {
+        match (option, value) {
+            ("report", "count") => self.report = Report::Count,
+            ("report", "overlaps") => self.report = Report::Overlaps,
+            ("report", "coverage") => self.report = Report::Coverage,
+            ("report", _) => {
+                return Err(AocError::invalid_option(
+                    option,
+                    "expected count, overlaps or coverage",
+                ))
+            }
+            ("k", k) => {
+                self.k = k
+                    .parse()
+                    .map_err(|e| AocError::invalid_option(option, format!("{}", e)))?
+            }
+            _ => {
+                return Err(AocError::invalid_option(
+                    option,
+                    "unknown option, day4 supports [report, k]",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn part1(&self, assignments: &Vec<CleaningAssignment>) -> Result<Answer, AocError> {
+        let contained_count = assignments.iter().filter(|a| a.pair_contained()).count();
+        Ok(contained_count.into())
+    }
+
+    fn part2(&self, assignments: &Vec<CleaningAssignment>) -> Result<Answer, AocError> {
+        match self.report {
+            Report::Count => {
+                let overlapping_count = assignments
+                    .iter()
+                    .filter(|a| a.pair_overlap().is_some())
+                    .count();
+                Ok(overlapping_count.into())
+            }
+            Report::Overlaps => Ok(Answer::Multiline(overlap_report(assignments))),
+            Report::Coverage => {
+                let sections = assignments.iter().flat_map(|a| [a.left, a.right]);
+                Ok(Answer::Multiline(
+                    Coverage::new(sections, self.k).to_string(),
+                ))
+            }
+        }
+    }
 }
 
 // DAY 4 END
+
+#[cfg(test)]
+mod day4_test {
+    use super::*;
+
+    #[test]
+    fn overlap_report_test() {
+        let assignments =
+            parse_lines("2-4,6-8\n5-7,7-9\n2-8,3-7", CleaningAssignment::from_input).unwrap();
+        assert_eq!(
+            overlap_report(&assignments),
+            "line 2: 5-7,7-9 overlap in 7-7 (1 sections)\nline 3: 2-8,3-7 overlap in 3-7 (5 sections, fully contained)"
+        );
+        assert_eq!(
+            parse_lines("2-4,8-6", CleaningAssignment::from_input).unwrap_err().to_string(),
+            "parse error at line 1, column 5: Invalid input, section range ends before it starts, got: '8-6'"
+        );
+    }
+    #[test]
+    fn coverage_test() {
+        let example = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+        let assignments = parse_lines(example, CleaningAssignment::from_input).unwrap();
+        let coverage = Coverage::new(assignments.iter().flat_map(|a| [a.left, a.right]), 5);
+        assert_eq!(coverage.span, Some(Interval::new(2, 9)));
+        assert_eq!(coverage.nobody, vec![]);
+        assert_eq!(coverage.exactly_one, vec![Interval::new(9, 9)]);
+        assert_eq!(coverage.more_than_k, vec![Interval::new(4, 7)]);
+
+        let gaps = Coverage::new(
+            [
+                Interval::new(1, 2),
+                Interval::new(5, 5),
+                Interval::new(3, 3),
+            ]
+            .into_iter(),
+            0,
+        );
+        assert_eq!(
+            gaps.to_string(),
+            "span: 1-5 (5 sections)\n\
+             covered by nobody: 1 sections in 1 ranges: 4-4\n\
+             covered by exactly one elf: 4 sections in 2 ranges: 1-3, 5-5\n\
+             covered by more than 0 elves: 4 sections in 2 ranges: 1-3, 5-5\n"
+        );
+        assert_eq!(
+            Coverage::new(std::iter::empty(), 2).to_string(),
+            "no sections assigned\n"
+        );
+    }
+}
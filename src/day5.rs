@@ -1,8 +1,10 @@
 use core::fmt;
-use std::{collections::HashMap, error::Error, num::ParseIntError};
+use std::{collections::HashMap, num::ParseIntError, str::FromStr};
+
+use crate::{Answer, AocError, Solution};
 
 // DAY 5
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Stack {
     id: usize,
     crates: Vec<char>,
@@ -10,10 +12,10 @@ struct Stack {
 
 impl Stack {
     fn new(id: usize) -> Stack {
-        return Stack {
-            id: id,
+        Stack {
+            id,
             crates: Vec::new(),
-        };
+        }
     }
     fn push(&mut self, c: char) {
         self.crates.push(c)
@@ -38,28 +40,38 @@ impl Stack {
 }
 
 #[derive(Debug)]
-struct MoveCmd {
+pub struct MoveCmd {
     count: i32,
     source: usize,
     destination: usize,
 }
 
 impl MoveCmd {
-    fn from_input(ls: &String) -> Result<MoveCmd, Box<dyn Error>> {
+    fn from_input(ls: &str) -> Result<MoveCmd, AocError> {
         let collect = ls.split_whitespace().collect::<Vec<&str>>();
         if let [_, count, _, source, _, destination] = collect.as_slice() {
             let cmd = MoveCmd {
-                count: count.parse()?,
-                source: source.parse()?,
-                destination: destination.parse()?,
+                count: parse_number(ls, count)?,
+                source: parse_number(ls, source)?,
+                destination: parse_number(ls, destination)?,
             };
             Ok(cmd)
         } else {
-            Err("Invalid MoveCmd input line".into())
+            Err(AocError::parse(
+                1,
+                ls,
+                "Invalid MoveCmd input line, expected: move N from S to D",
+            ))
         }
     }
 }
 
+fn parse_number<T: FromStr<Err = ParseIntError>>(line: &str, token: &str) -> Result<T, AocError> {
+    token
+        .parse()
+        .map_err(|e| AocError::parse_token(line, token, format!("invalid number, {}", e)))
+}
+
 impl fmt::Display for MoveCmd {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -70,15 +82,22 @@ impl fmt::Display for MoveCmd {
     }
 }
 
-#[derive(Debug)]
-struct SupplyStacks {
+/// Model of the crane, 9000 moves crates one at a time while 9001 keeps their order.
+#[derive(Debug, Clone, Copy)]
+enum CrateMover {
+    Model9000,
+    Model9001,
+}
+
+#[derive(Debug, Clone)]
+pub struct SupplyStacks {
     stacks: HashMap<usize, Stack>,
 }
 
 impl fmt::Display for SupplyStacks {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let max_crate = self.stacks.values().map(|v| v.crates.len()).max();
-        let mut stack_idx: Vec<usize> = self.stacks.keys().map(|&k| k).collect();
+        let mut stack_idx: Vec<usize> = self.stacks.keys().copied().collect();
         stack_idx.sort();
 
         if let Some(mc) = max_crate {
@@ -93,7 +112,7 @@ impl fmt::Display for SupplyStacks {
                     .collect();
 
                 if let Some(cs) = line {
-                    write!(f, "{}\n", cs.join(" "))?
+                    writeln!(f, "{}", cs.join(" "))?
                 }
             }
         }
@@ -110,11 +129,20 @@ impl fmt::Display for SupplyStacks {
 }
 
 impl SupplyStacks {
-    fn from_input(mut ls: Vec<&String>) -> Result<SupplyStacks, Box<dyn Error>> {
+    fn from_input(mut ls: Vec<&str>) -> Result<SupplyStacks, AocError> {
+        let numbers_line = ls.len();
         ls.reverse();
-        let stack_nums: Result<Vec<usize>, ParseIntError> = ls[0]
+        let stack_ids = ls
+            .first()
+            .ok_or("Invalid input, missing supply stacks drawing")?;
+        let stack_nums: Result<Vec<usize>, AocError> = stack_ids
             .split_whitespace()
-            .map(|i| i.parse::<usize>())
+            .map(|i| {
+                i.parse::<usize>().map_err(|e| {
+                    AocError::parse_token(stack_ids, i, format!("invalid stack number, {}", e))
+                        .on_line(numbers_line)
+                })
+            })
             .collect();
         // let stacks:Vec<Stack> = stack_nums?.iter().map(|i| Stack::new(*i)).collect();
 
@@ -135,7 +163,7 @@ impl SupplyStacks {
                 })
                 .collect(),
         };
-        return Ok(supply_stacks);
+        Ok(supply_stacks)
     }
 
     fn push_line(stacks_map: &mut HashMap<usize, Vec<char>>, l: &str) {
@@ -161,64 +189,90 @@ impl SupplyStacks {
                     let &k = stack_2_column
                         .get(&i)
                         .expect("failed to get stack idx key ");
-                    stacks_map.entry(k).or_insert(vec![]).push(c)
+                    stacks_map.entry(k).or_default().push(c)
                 }
             });
     }
 
-    fn apply(&mut self, cmd: &MoveCmd) -> Option<()> {
+    fn apply(&mut self, cmd: &MoveCmd, crane: CrateMover) -> Option<()> {
         let source = self.stacks.get_mut(&cmd.source)?;
         let cs = source.pop_n(cmd.count)?;
 
         let target = self.stacks.get_mut(&cmd.destination)?;
-        target.push_n(cs);
-        // for _i in 0..cmd.count {
-        //     let source = self.stacks.get_mut(&cmd.source)?;
-        //     // let c = source.pop()?;
-        //     // let target = self.stacks.get_mut(&cmd.destination)?;
-        //     let cs = source.pop_n(cmd.count)
-        //     target.push(c);
-        // }
+        match crane {
+            CrateMover::Model9000 => cs.into_iter().for_each(|c| target.push(c)),
+            CrateMover::Model9001 => target.push_n(cs),
+        }
         Some(())
     }
 
-    fn top_of_stacks(&self) -> String {
+    fn top_of_stacks(&self) -> Result<String, AocError> {
         let mut top_crates: Vec<(usize, &char)> = self
             .stacks
             .values()
-            .map(|vs| (vs.id, vs.crates.last().ok_or("").unwrap()))
-            .collect();
-        top_crates.sort_by(|(id_a, _), (id_b, _)| id_a.cmp(id_b));
+            .map(|vs| {
+                let top = vs.crates.last().ok_or(format!(
+                    "stack {} ends up empty, it has no top crate",
+                    vs.id
+                ))?;
+                Ok((vs.id, top))
+            })
+            .collect::<Result<_, AocError>>()?;
+        top_crates.sort_by_key(|(id, _)| *id);
 
-        top_crates
+        Ok(top_crates
             .iter()
             .map(|(_, s)| s.to_string())
             .collect::<Vec<String>>()
-            .join("")
+            .join(""))
     }
 }
 
-pub fn result(lines: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let stacks_inputs: Vec<&String> = lines.iter().take_while(|&l| l != "").collect();
-    let cmd_inputs: Vec<&String> = lines.iter().skip_while(|&l| l != "").skip(1).collect();
-    let mut stacks = SupplyStacks::from_input(stacks_inputs)?;
-    println!("SupplyStacks:\n{}", stacks);
-    let cmds: Result<Vec<MoveCmd>, Box<dyn Error>> =
-        cmd_inputs.iter().map(|&l| MoveCmd::from_input(l)).collect();
-    let cmds = cmds?;
-    let r: Result<(), String> = cmds
-        .iter()
-        .map(|cmd| {
-            stacks.apply(cmd).ok_or(format!(
+pub struct Day5;
+
+impl Day5 {
+    fn rearrange(
+        (stacks, cmds): &(SupplyStacks, Vec<MoveCmd>),
+        crane: CrateMover,
+    ) -> Result<Answer, AocError> {
+        let mut stacks = stacks.clone();
+        for cmd in cmds {
+            stacks.apply(cmd, crane).ok_or(format!(
                 "Failed to apply cmd:\n{}\n, stacks:\n{}",
                 cmd, stacks
-            ))
-        })
-        .collect();
+            ))?;
+        }
+        Ok(Answer::Text(stacks.top_of_stacks()?))
+    }
+}
+
+impl Solution for Day5 {
+    type Input = (SupplyStacks, Vec<MoveCmd>);
+
+    fn parse(&self, input: &str) -> Result<(SupplyStacks, Vec<MoveCmd>), AocError> {
+        let stacks_inputs: Vec<&str> = input.lines().take_while(|&l| !l.is_empty()).collect();
+        // commands start after the drawing and the blank line separating them
+        let first_cmd_line = stacks_inputs.len() + 2;
+        let cmd_inputs: Vec<&str> = input
+            .lines()
+            .skip_while(|&l| !l.is_empty())
+            .skip(1)
+            .collect();
+        let stacks = SupplyStacks::from_input(stacks_inputs)?;
+        let cmds: Result<Vec<MoveCmd>, AocError> = cmd_inputs
+            .iter()
+            .enumerate()
+            .map(|(i, &l)| MoveCmd::from_input(l).map_err(|e| e.on_line(first_cmd_line + i)))
+            .collect();
+        Ok((stacks, cmds?))
+    }
+
+    fn part1(&self, input: &(SupplyStacks, Vec<MoveCmd>)) -> Result<Answer, AocError> {
+        Day5::rearrange(input, CrateMover::Model9000)
+    }
 
-    if r.is_ok() {
-        println!("Result: {}", stacks.top_of_stacks());
+    fn part2(&self, input: &(SupplyStacks, Vec<MoveCmd>)) -> Result<Answer, AocError> {
+        Day5::rearrange(input, CrateMover::Model9001)
     }
-    r.or_else(|e| Err(e.into()))
 }
 // DAY 5 END
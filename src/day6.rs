@@ -1,7 +1,8 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    error::Error,
-};
+use std::collections::{HashSet, VecDeque};
+
+use itertools::Itertools;
+
+use crate::{Answer, AocError, Solution};
 
 struct MarkerDetector {
     ring_buffer: VecDeque<char>,
@@ -11,11 +12,11 @@ struct MarkerDetector {
 
 impl MarkerDetector {
     fn new(l: usize) -> MarkerDetector {
-        return MarkerDetector {
+        MarkerDetector {
             marker_length: l,
             ring_buffer: VecDeque::with_capacity(l),
             chars_processed: 0,
-        };
+        }
     }
 
     fn process(&mut self, c: char) -> bool {
@@ -29,12 +30,36 @@ impl MarkerDetector {
     }
 }
 
-pub fn result(lines: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let marker_len = 14;
-    for l in lines {
-        let mut sop = MarkerDetector::new(marker_len);
-        l.chars().take_while(|&c| !sop.process(c)).count();
-        println!("Result: {}", sop.chars_processed);
+fn marker_positions(lines: &[String], marker_len: usize) -> Answer {
+    let positions: Vec<usize> = lines
+        .iter()
+        .map(|l| {
+            let mut sop = MarkerDetector::new(marker_len);
+            l.chars().take_while(|&c| !sop.process(c)).count();
+            sop.chars_processed
+        })
+        .collect();
+    match positions[..] {
+        [single] => single.into(),
+        // one datastream per line, report a marker for each of them
+        _ => Answer::Multiline(positions.iter().join("\n")),
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, AocError> {
+        Ok(input.lines().map(|l| l.to_string()).collect())
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<Answer, AocError> {
+        Ok(marker_positions(lines, 4))
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> Result<Answer, AocError> {
+        Ok(marker_positions(lines, 14))
     }
-    Ok(())
 }
@@ -1,14 +1,16 @@
-use std::{collections::HashMap, error::Error};
+use std::collections::HashMap;
 
 use pest::{iterators::Pair, Parser};
 
+use crate::{Answer, AocError, Solution};
+
 #[derive(Parser)]
 #[grammar = "day7.pest"]
 pub struct FsCmdParser;
 
 #[derive(Debug)]
 pub enum LsCmdOutput {
-    DirOutput(String),
+    DirOutput,
     FileOutput(String, usize),
 }
 #[derive(Debug)]
@@ -19,13 +21,13 @@ pub enum FsCmd {
     Ls(Vec<LsCmdOutput>),
 }
 
-fn parse_cmds(e: Pair<Rule>) -> Result<FsCmd, &str> {
+fn parse_cmds(e: Pair<'_, Rule>) -> Result<FsCmd, &'static str> {
     match e.as_rule() {
         Rule::lsCmd => {
             let output_lines: Result<Vec<LsCmdOutput>, &str> = e
                 .into_inner()
                 .map(|l| match l.as_rule() {
-                    Rule::dirOutput => Ok(LsCmdOutput::DirOutput(l.as_str().to_string())),
+                    Rule::dirOutput => Ok(LsCmdOutput::DirOutput),
                     Rule::fileOutput => {
                         let mut inner = l.into_inner();
                         let file_size = inner
@@ -92,7 +94,7 @@ impl ElfFs {
                 } else {
                     let parent = acc.last().unwrap();
                     let path = format!("{}{}/", parent, p);
-                    acc.into_iter().chain(vec![path].into_iter()).collect()
+                    acc.into_iter().chain(std::iter::once(path)).collect()
                 }
             });
             for dir in absolute_paths {
@@ -102,10 +104,10 @@ impl ElfFs {
         dirs
     }
 
-    fn populate_files(&mut self, cs: &Vec<LsCmdOutput>) -> &mut ElfFs {
+    fn populate_files(&mut self, cs: &[LsCmdOutput]) -> &mut ElfFs {
         for o in cs {
             match o {
-                LsCmdOutput::DirOutput(_) => (),
+                LsCmdOutput::DirOutput => (),
                 LsCmdOutput::FileOutput(n, s) => {
                     let mut path = self.cwd.clone();
                     path.push(n.to_string());
@@ -132,40 +134,56 @@ impl ElfFs {
     }
 }
 
-pub fn result(inputs: String) -> Result<(), Box<dyn Error>> {
-    let parsed = FsCmdParser::parse(Rule::fsCmd, &inputs)?;
-
-    let cmds: Result<Vec<FsCmd>, &str> = parsed.map(parse_cmds).collect();
-    let cmds = cmds?;
-    let mut init = ElfFs::empty();
-    let fs = cmds.iter().fold(&mut init, ElfFs::fold_cmd);
-    let dir_sizes = fs.dir_sizes();
-
-    let result: usize = dir_sizes
-        .iter()
-        .filter(|(_, &size)| size <= 100000)
-        .map(|(_, &size)| size)
-        .sum();
-
-    println!("Result: {}", result);
-    let total_fs_size: usize = 70000000;
-    let required_free_space: usize = 30000000;
-    let root_dir_size = dir_sizes
-        .get("/")
-        .ok_or("Cannot find / (root) in dir_sizes")?;
-    let free_space = total_fs_size - root_dir_size;
-    let space_needed_to_free = required_free_space - free_space;
-    println!("Space needed to free: {}", space_needed_to_free);
-    let mut big_enough_dirs: Vec<usize> = dir_sizes
-        .iter()
-        .filter(|(_, &size)| size >= space_needed_to_free)
-        .map(|(_, &s)| s)
-        .collect();
-    big_enough_dirs.sort();
-    let res_s = big_enough_dirs
-        .first()
-        .ok_or("empty big_enough_dirs vec, no dirs are big enough!")?;
-    println!("Part2 result:  {}", res_s);
-
-    Ok(())
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = HashMap<String, usize>;
+
+    fn parse(&self, inputs: &str) -> Result<HashMap<String, usize>, AocError> {
+        let parsed = FsCmdParser::parse(Rule::fsCmd, inputs)?;
+
+        let cmds: Result<Vec<FsCmd>, AocError> = parsed
+            .map(|p| {
+                let (line, column) = p.as_span().start_pos().line_col();
+                let text = p.as_str().lines().next().unwrap_or_default().to_string();
+                parse_cmds(p).map_err(|e| AocError::parse(column, &text, e).on_line(line))
+            })
+            .collect();
+        let cmds = cmds?;
+        let mut init = ElfFs::empty();
+        let fs = cmds.iter().fold(&mut init, ElfFs::fold_cmd);
+        Ok(fs.dir_sizes())
+    }
+
+    fn part1(&self, dir_sizes: &HashMap<String, usize>) -> Result<Answer, AocError> {
+        let result: usize = dir_sizes
+            .iter()
+            .filter(|(_, &size)| size <= 100000)
+            .map(|(_, &size)| size)
+            .sum();
+        Ok(result.into())
+    }
+
+    fn part2(&self, dir_sizes: &HashMap<String, usize>) -> Result<Answer, AocError> {
+        let total_fs_size: usize = 70000000;
+        let required_free_space: usize = 30000000;
+        let root_dir_size = dir_sizes
+            .get("/")
+            .ok_or("Cannot find / (root) in dir_sizes")?;
+        let free_space = total_fs_size
+            .checked_sub(*root_dir_size)
+            .ok_or("files take more space than the whole file system has")?;
+        // nothing needs deleting when there's enough free space already, the smallest dir still gets picked
+        let space_needed_to_free = required_free_space.saturating_sub(free_space);
+        let mut big_enough_dirs: Vec<usize> = dir_sizes
+            .iter()
+            .filter(|(_, &size)| size >= space_needed_to_free)
+            .map(|(_, &s)| s)
+            .collect();
+        big_enough_dirs.sort();
+        let res_s = big_enough_dirs
+            .first()
+            .ok_or("empty big_enough_dirs vec, no dirs are big enough!")?;
+        Ok((*res_s).into())
+    }
 }
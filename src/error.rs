@@ -0,0 +1,268 @@
+use core::fmt;
+use std::{error::Error, io};
+
+use pest::{error::LineColLocation, RuleType};
+
+/// Every error the solutions can produce, parse errors point at the offending spot of the puzzle input.
+#[derive(Debug)]
+pub enum AocError {
+    /// Malformed input, `line` and `column` are 1 based, `line` is 0 until the caller knows which line was parsed.
+    Parse {
+        day: Option<String>,
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// Well formed input which can't be solved, i.e. no path between S and E.
+    Invalid {
+        day: Option<String>,
+        message: String,
+    },
+    InvalidOption {
+        day: Option<String>,
+        option: String,
+        message: String,
+    },
+    UnknownDay(String),
+    Io(io::Error),
+}
+
+impl AocError {
+    pub fn parse(column: usize, text: &str, message: impl Into<String>) -> AocError {
+        AocError::Parse {
+            day: None,
+            line: 0,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Parse error about `token`, which has to be a slice of `line`, so the column can be worked out from it.
+    pub fn parse_token(line: &str, token: &str, message: impl Into<String>) -> AocError {
+        AocError::parse(column_of(line, token), token, message)
+    }
+
+    pub fn invalid_option(option: &str, message: impl Into<String>) -> AocError {
+        AocError::InvalidOption {
+            day: None,
+            option: option.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Sets the line of a parse error, for parsers which only ever see a single line.
+    pub fn on_line(self, line_no: usize) -> AocError {
+        match self {
+            AocError::Parse {
+                day,
+                column,
+                text,
+                message,
+                ..
+            } => AocError::Parse {
+                day,
+                line: line_no,
+                column,
+                text,
+                message,
+            },
+            e => e,
+        }
+    }
+
+    /// Moves the column of a parse error reported against `part`, a slice of `line`, so it points into the whole line.
+    pub fn within(self, line: &str, part: &str) -> AocError {
+        match self {
+            AocError::Parse {
+                day,
+                line: line_no,
+                column,
+                text,
+                message,
+            } => AocError::Parse {
+                day,
+                line: line_no,
+                column: column + column_of(line, part) - 1,
+                text,
+                message,
+            },
+            e => e,
+        }
+    }
+
+    pub fn in_day(self, aoc_day: &str) -> AocError {
+        let day = Some(aoc_day.to_string());
+        match self {
+            AocError::Parse {
+                line,
+                column,
+                text,
+                message,
+                ..
+            } => AocError::Parse {
+                day,
+                line,
+                column,
+                text,
+                message,
+            },
+            AocError::Invalid { message, .. } => AocError::Invalid { day, message },
+            AocError::InvalidOption {
+                option, message, ..
+            } => AocError::InvalidOption {
+                day,
+                option,
+                message,
+            },
+            e => e,
+        }
+    }
+}
+
+// 1 based column of token within line, token has to be a sub slice of line
+pub(crate) fn column_of(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line.get(..offset)
+        .map_or(1, |prefix| prefix.chars().count() + 1)
+}
+
+/// Parses every line of `input` with `parse_line`, failures are reported at their 1 based line number.
+pub(crate) fn parse_lines<T>(
+    input: &str,
+    parse_line: impl Fn(&str) -> Result<T, AocError>,
+) -> Result<Vec<T>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| parse_line(l).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let day_prefix =
+            |day: &Option<String>| day.as_ref().map_or(String::new(), |d| format!("{}: ", d));
+        match self {
+            AocError::Parse {
+                day,
+                line,
+                column,
+                text,
+                message,
+            } => write!(
+                f,
+                "{}parse error at line {}, column {}: {}, got: '{}'",
+                day_prefix(day),
+                line,
+                column,
+                message,
+                text
+            ),
+            AocError::Invalid { day, message } => write!(f, "{}{}", day_prefix(day), message),
+            AocError::InvalidOption {
+                day,
+                option,
+                message,
+            } => write!(
+                f,
+                "{}invalid option {}: {}",
+                day_prefix(day),
+                option,
+                message
+            ),
+            AocError::UnknownDay(day) => write!(
+                f,
+                "Not implemented Advent Of Code Day selected: {}, currently supported: [{}]",
+                day,
+                crate::days().collect::<Vec<&str>>().join(",")
+            ),
+            AocError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+impl From<&str> for AocError {
+    fn from(message: &str) -> Self {
+        AocError::Invalid {
+            day: None,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<String> for AocError {
+    fn from(message: String) -> Self {
+        AocError::Invalid { day: None, message }
+    }
+}
+
+impl<R: RuleType> From<pest::error::Error<R>> for AocError {
+    fn from(e: pest::error::Error<R>) -> Self {
+        let (line, column) = match e.line_col {
+            LineColLocation::Pos(pos) => pos,
+            LineColLocation::Span(start, _) => start,
+        };
+        AocError::Parse {
+            day: None,
+            line,
+            column,
+            text: e.line().to_string(),
+            message: e.variant.message().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod error_test {
+    use super::*;
+
+    #[test]
+    fn parse_lines_reports_line_and_column() {
+        let res = parse_lines("1 2\n3 x", |l| {
+            l.split(' ')
+                .map(|t| {
+                    t.parse::<u32>()
+                        .map_err(|_| AocError::parse_token(l, t, "not a number"))
+                })
+                .collect::<Result<Vec<u32>, AocError>>()
+        });
+        match res {
+            Err(AocError::Parse {
+                line, column, text, ..
+            }) => {
+                assert_eq!((line, column, text.as_str()), (2, 3, "x"));
+            }
+            other => panic!("expected parse error, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn solve_tags_errors_with_day() {
+        let err = crate::solve("day4", "2-4,6-8\n2-3,4-x5", &[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day4: parse error at line 2, column 7: invalid section id, invalid digit found in string, got: 'x5'"
+        );
+        assert!(matches!(
+            crate::solve("day42", "", &[]),
+            Err(AocError::UnknownDay(_))
+        ));
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use crate::{parse_options, run::input_path};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Minimal HTTP client, a trait so tests can stand in for adventofcode.com.
+pub trait Http {
+    /// Body of a GET to `url` made with the `session` cookie, anything but a 2xx response is an error.
+    fn get(&self, url: &str, session: &str) -> Result<String, String>;
+}
+
+/// [`Http`] client shelling out to `curl`, which handles TLS so we don't need an HTTP crate.
+pub struct Curl;
+
+impl Http for Curl {
+    fn get(&self, url: &str, session: &str) -> Result<String, String> {
+        // the cookie goes through stdin, so the token doesn't show up in the process list
+        let mut curl = Command::new("curl")
+            .args([
+                "--silent",
+                "--show-error",
+                "--fail",
+                "--location",
+                "--header",
+                "@-",
+            ])
+            .args([
+                "--user-agent",
+                "github.com/nabacg/adv-of-code-2022 via curl",
+            ])
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to run curl: {}", e))?;
+        curl.stdin
+            .take()
+            .ok_or("curl stdin is not piped")?
+            .write_all(format!("Cookie: session={}\n", session).as_bytes())
+            .map_err(|e| format!("failed to pass session cookie to curl: {}", e))?;
+
+        let output = curl
+            .wait_with_output()
+            .map_err(|e| format!("failed to run curl: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "GET {} failed: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        String::from_utf8(output.stdout)
+            .map_err(|e| format!("GET {} returned invalid UTF-8: {}", url, e))
+    }
+}
+
+/// Session token and server to download inputs from.
+///
+/// Read from `~/.config/adv-of-code-2022/config`, holding `key=value` lines:
+/// ```text
+/// session=53616c7465645f5f...
+/// base_url=http://localhost:8080
+/// ```
+/// The `AOC_SESSION` and `AOC_BASE_URL` environment variables take precedence over the file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchConfig {
+    pub session: Option<String>,
+    pub base_url: String,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        FetchConfig {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+        }
+    }
+}
+
+impl FetchConfig {
+    pub fn parse(text: &str) -> Result<FetchConfig, String> {
+        let lines = text
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+        let mut config = FetchConfig::default();
+        for (key, value) in parse_options(lines)? {
+            match key.as_str() {
+                "session" => config.session = Some(value),
+                "base_url" => config.base_url = value,
+                _ => {
+                    return Err(format!(
+                        "unknown config key: {}, supported: [session, base_url]",
+                        key
+                    ))
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn load() -> Result<FetchConfig, String> {
+        let mut config = match config_path() {
+            Some(path) if path.exists() => fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| FetchConfig::parse(&text))
+                .map_err(|e| format!("invalid config file {}: {}", path.display(), e))?,
+            _ => FetchConfig::default(),
+        };
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| Path::new(&home).join(".config/adv-of-code-2022/config"))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    /// The input was already there, nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads a day's input into `inputs_dir/dayN/input.txt`, unless it's already there.
+///
+/// An empty `input.txt`, as left by `new dayN`, doesn't count as cached.
+pub fn fetch(
+    http: &dyn Http,
+    config: &FetchConfig,
+    inputs_dir: &Path,
+    aoc_day: &str,
+) -> Result<Fetched, String> {
+    let day_no = aoc_day
+        .strip_prefix("day")
+        .and_then(|n| n.parse::<u32>().ok())
+        .filter(|n| (1..=25).contains(n))
+        .ok_or(format!("invalid day: {}, expected day1 .. day25", aoc_day))?;
+
+    let path = input_path(inputs_dir, aoc_day);
+    if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let session = config.session.as_ref().ok_or(
+        "missing session token, set AOC_SESSION or session=... in ~/.config/adv-of-code-2022/config",
+    )?;
+    let url = format!(
+        "{}/2022/day/{}/input",
+        config.base_url.trim_end_matches('/'),
+        day_no
+    );
+    let input = http.get(&url, session)?;
+    if input.is_empty() {
+        return Err(format!("GET {} returned an empty input", url));
+    }
+
+    let dir = path.parent().ok_or("input path has no parent directory")?;
+    fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    fs::write(&path, input).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod fetch_test {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    struct StubHttp {
+        requests: RefCell<Vec<(String, String)>>,
+    }
+
+    impl Http for StubHttp {
+        fn get(&self, url: &str, session: &str) -> Result<String, String> {
+            self.requests
+                .borrow_mut()
+                .push((url.to_string(), session.to_string()));
+            Ok("1000\n2000\n".to_string())
+        }
+    }
+
+    #[test]
+    fn fetch_caches_input_test() {
+        let inputs_dir = env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let http = StubHttp {
+            requests: RefCell::new(vec![]),
+        };
+        let config =
+            FetchConfig::parse("# local stub\nsession=abc\nbase_url=http://localhost:8080/")
+                .unwrap();
+
+        let path = inputs_dir.join("day3").join("input.txt");
+        assert_eq!(
+            fetch(&http, &config, &inputs_dir, "day3"),
+            Ok(Fetched::Downloaded(path.clone()))
+        );
+        assert_eq!(
+            fetch(&http, &config, &inputs_dir, "day3"),
+            Ok(Fetched::Cached(path.clone()))
+        );
+        assert_eq!(
+            *http.requests.borrow(),
+            vec![(
+                "http://localhost:8080/2022/day/3/input".to_string(),
+                "abc".to_string()
+            )]
+        );
+        assert_eq!(fs::read_to_string(path).unwrap(), "1000\n2000\n");
+        assert!(fetch(&http, &FetchConfig::default(), &inputs_dir, "day4").is_err());
+
+        fs::remove_dir_all(inputs_dir).unwrap();
+    }
+}
@@ -0,0 +1,233 @@
+use core::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Integer point on the puzzle plane, `y` grows downwards like rows of the input do.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    /// Taxicab distance, the number of 4-neighbour steps between the points.
+    pub fn manhattan(&self, other: Point) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Chessboard distance, the number of 8-neighbour steps between the points.
+    pub fn chebyshev(&self, other: Point) -> u32 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Each coordinate clamped to -1, 0 or 1, i.e. a single step from one point towards another.
+    pub fn signum(&self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn neighbours4(&self) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL.iter().map(move |d| *self + d.delta())
+    }
+
+    pub fn neighbours8(&self) -> impl Iterator<Item = Point> + '_ {
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| Point::new(x, y)))
+            .filter(|&d| d != Point::ORIGIN)
+            .map(move |d| *self + d)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, n: i32) -> Point {
+        Point::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// One of the 4 compass directions, `Up` lowers `y` since rows are counted downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Single step in this direction.
+    pub fn delta(&self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        self.turn_right().opposite()
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.turn_right().turn_right()
+    }
+}
+
+/// Smallest rectangle holding a set of points, both corners are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    /// None for no points at all.
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Option<BoundingBox> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bbox = BoundingBox {
+            min: first,
+            max: first,
+        };
+        points.for_each(|p| bbox.include(p));
+        Some(bbox)
+    }
+
+    /// Grows the box so it holds `p`.
+    pub fn include(&mut self, p: Point) {
+        self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+        self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+
+    pub fn width(&self) -> u32 {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> u32 {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+
+    /// Every point of the box, row by row from the top left corner.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod geom_test {
+    use super::*;
+
+    #[test]
+    fn point_test() {
+        let a = Point::new(1, -2);
+        let b = Point::new(-3, 4);
+        assert_eq!(a + b, Point::new(-2, 2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(-a * 2, Point::new(-2, 4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!((b - a).signum(), Point::new(-1, 1));
+        assert_eq!(a.neighbours8().count(), 8);
+        assert!(a.neighbours4().all(|n| n.manhattan(a) == 1));
+    }
+
+    #[test]
+    fn direction_test() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert!(Direction::ALL
+            .iter()
+            .all(|d| d.delta() + d.opposite().delta() == Point::ORIGIN));
+    }
+
+    #[test]
+    fn bounding_box_test() {
+        let bbox =
+            BoundingBox::from_points([Point::new(2, 3), Point::new(-1, 5), Point::new(0, 4)])
+                .unwrap();
+        assert_eq!(bbox.min, Point::new(-1, 3));
+        assert_eq!(bbox.max, Point::new(2, 5));
+        assert_eq!((bbox.width(), bbox.height()), (4, 3));
+        assert!(bbox.contains(Point::new(0, 3)));
+        assert!(!bbox.contains(Point::new(3, 3)));
+        assert_eq!(bbox.points().count(), 12);
+        assert_eq!(BoundingBox::from_points(vec![]), None);
+    }
+}
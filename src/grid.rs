@@ -0,0 +1,220 @@
+use core::fmt;
+
+use crate::{geom::Point, AocError};
+
+/// Position of a cell, as (row, column) counted from the top left corner.
+pub type Pos = (usize, usize);
+
+/// Rectangular grid of cells stored row by row, i.e. day8's tree heights or day12's height map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(cells: Vec<T>, height: usize, width: usize) -> Self {
+        assert!(cells.len() == height * width);
+        Grid {
+            cells,
+            height,
+            width,
+        }
+    }
+
+    /// One row per line and one cell per char, every row has to be as long as the first one.
+    /// Cells `parse_cell` rejects are reported at their line and column.
+    pub fn parse(
+        text: &str,
+        parse_cell: impl Fn(char) -> Result<T, String>,
+    ) -> Result<Grid<T>, AocError> {
+        let width = text.lines().next().map_or(0, |l| l.chars().count());
+        let mut cells = Vec::with_capacity(text.len());
+        let mut height = 0;
+        for (i, l) in text.lines().enumerate() {
+            if l.chars().count() != width {
+                return Err(AocError::parse(
+                    1,
+                    l,
+                    format!("expected {} cells in every row", width),
+                )
+                .on_line(i + 1));
+            }
+            for (j, c) in l.chars().enumerate() {
+                let cell = parse_cell(c)
+                    .map_err(|e| AocError::parse(j + 1, &c.to_string(), e).on_line(i + 1))?;
+                cells.push(cell);
+            }
+            height += 1;
+        }
+        if cells.is_empty() {
+            return Err("empty grid".into());
+        }
+        Ok(Grid::new(cells, height, width))
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The cell at `(row, col)`, None when it lies outside the grid.
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        if row < self.height && col < self.width {
+            self.cells.get(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    /// Like [`Grid::get`], with `x` as the column and `y` as the row.
+    pub fn get_point(&self, p: Point) -> Option<&T> {
+        self.get((usize::try_from(p.y).ok()?, usize::try_from(p.x).ok()?))
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        if row < self.height && col < self.width {
+            self.cells.get_mut(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    /// Positions of all cells, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.height).flat_map(move |row| (0..self.width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Positions above, right, below and left of `pos`, skipping those outside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &[(-1, 0), (0, 1), (1, 0), (0, -1)])
+    }
+
+    /// Like [`Grid::neighbours4`], plus the diagonal neighbours.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(
+            pos,
+            &[
+                (-1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+            ],
+        )
+    }
+
+    fn neighbours(
+        &self,
+        (row, col): Pos,
+        deltas: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + '_ {
+        deltas.iter().filter_map(move |&(d_row, d_col)| {
+            let pos = (
+                row.checked_add_signed(d_row)?,
+                col.checked_add_signed(d_col)?,
+            );
+            self.get(pos).map(|_| pos)
+        })
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Cells of a column, from the top.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.cells[col..]
+            .iter()
+            .step_by(self.width)
+            .take(self.height)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + ExactSizeIterator> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Grid of the same shape, with every cell converted by `f`.
+    pub fn map<U>(&self, f: impl Fn(Pos, &T) -> U) -> Grid<U> {
+        let cells = self.iter().map(|(pos, cell)| f(pos, cell)).collect();
+        Grid::new(cells, self.height, self.width)
+    }
+
+    /// Grid mirrored along its diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flat_map(|c| c.cloned()).collect();
+        Grid::new(cells, self.width, self.height)
+    }
+}
+
+/// Renders each row on its own line, cells are written next to each other without separators.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod grid_test {
+    use super::*;
+
+    fn digits(text: &str) -> Result<Grid<u32>, AocError> {
+        Grid::parse(text, |c| c.to_digit(10).ok_or("not a digit".to_string()))
+    }
+
+    #[test]
+    fn grid_test() {
+        let g = digits("123\n456").unwrap();
+        assert_eq!((g.height(), g.width()), (2, 3));
+        assert_eq!(g.get((1, 2)), Some(&6));
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(
+            g.neighbours4((0, 0)).collect::<Vec<Pos>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(g.neighbours8((1, 1)).count(), 5);
+        assert_eq!(g.row(1), &[4, 5, 6]);
+        assert_eq!(g.column(2).rev().collect::<Vec<&u32>>(), vec![&6, &3]);
+        assert_eq!(g.transpose().to_string(), "14\n25\n36");
+        assert_eq!(g.transpose().transpose(), g);
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        assert_eq!(
+            digits("12\n3x").unwrap_err().to_string(),
+            "parse error at line 2, column 2: not a digit, got: 'x'"
+        );
+        assert!(digits("12\n345").is_err());
+        assert!(digits("").is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    fmt, fs,
+    io::{self, BufRead, BufReader, Read},
+    path::PathBuf,
+};
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    /// `-` means stdin, anything else is a path to the input file.
+    pub fn from_arg(arg: &str) -> InputSource {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Inline input from the command line, `\n` escapes are turned into new lines so multi line inputs fit in one argument.
+    pub fn inline(s: &str) -> InputSource {
+        InputSource::Inline(s.replace("\\n", "\n"))
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::Inline(s) => Ok(s.clone()),
+        }
+    }
+
+    /// Buffered reader over the input, so it can be processed without reading it all at once.
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead + '_>> {
+        match self {
+            InputSource::File(path) => Ok(Box::new(BufReader::new(fs::File::open(path)?))),
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::Inline(s) => Ok(Box::new(s.as_bytes())),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Inline(_) => write!(f, "<inline>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod input_test {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn input_source_test() {
+        let inline = InputSource::inline("1000\\n2000\\n\\n3000");
+        assert_eq!(inline.read().unwrap(), "1000\n2000\n\n3000");
+        let lines: Vec<String> = inline
+            .reader()
+            .unwrap()
+            .lines()
+            .map(|l| l.unwrap())
+            .collect();
+        assert_eq!(lines, inline.read().unwrap().lines().collect::<Vec<&str>>());
+
+        assert!(matches!(InputSource::from_arg("-"), InputSource::Stdin));
+        assert!(matches!(
+            InputSource::from_arg("inputs/day1/input.txt"),
+            InputSource::File(path) if path == Path::new("inputs/day1/input.txt")
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+use core::fmt;
+
+/// Inclusive range of integers, i.e. day4's section ids or the columns a day15 sensor covers in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Interval {
+        assert!(
+            start <= end,
+            "interval start {} is after its end {}",
+            start,
+            end
+        );
+        Interval { start, end }
+    }
+
+    /// Number of integers in the interval, both ends included.
+    pub fn len(&self) -> u64 {
+        self.start.abs_diff(self.end) + 1
+    }
+
+    /// Intervals always hold at least their start.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn contains_value(&self, value: i64) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Values in both intervals, None when they don't overlap.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        self.overlaps(other)
+            .then(|| Interval::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+
+    /// Single interval holding the values of both, None when there is a gap between them.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        // touching intervals, i.e. 2-4 and 5-6, join without a gap
+        let touching = self.end.checked_add(1).is_some_and(|e| e >= other.start)
+            && other.end.checked_add(1).is_some_and(|e| e >= self.start);
+        touching.then(|| Interval::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// Values of `self` which aren't in `other`, up to 2 intervals when `other` cuts `self` in the middle.
+    pub fn difference(&self, other: &Interval) -> Vec<Interval> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+        let mut rest = vec![];
+        if self.start < other.start {
+            rest.push(Interval::new(self.start, other.start - 1));
+        }
+        if other.end < self.end {
+            rest.push(Interval::new(other.end + 1, self.end));
+        }
+        rest
+    }
+
+    /// Sorted intervals covering the same values, with overlapping and touching intervals joined.
+    pub fn merge(intervals: impl IntoIterator<Item = Interval>) -> Vec<Interval> {
+        let mut intervals: Vec<Interval> = intervals.into_iter().collect();
+        intervals.sort_unstable();
+        intervals.into_iter().fold(vec![], |mut merged, i| {
+            match merged
+                .last_mut()
+                .and_then(|last: &mut Interval| last.union(&i).map(|u| (last, u)))
+            {
+                Some((last, union)) => *last = union,
+                None => merged.push(i),
+            }
+            merged
+        })
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod interval_test {
+    use super::*;
+
+    #[test]
+    fn interval_test() {
+        let a = Interval::new(2, 8);
+        let b = Interval::new(3, 7);
+        let c = Interval::new(6, 10);
+        assert_eq!(a.len(), 7);
+        assert!(a.contains(&b) && !b.contains(&a));
+        assert_eq!(a.intersection(&c), Some(Interval::new(6, 8)));
+        assert_eq!(b.intersection(&Interval::new(8, 9)), None);
+        assert_eq!(a.union(&c), Some(Interval::new(2, 10)));
+        assert_eq!(b.union(&Interval::new(8, 9)), Some(Interval::new(3, 9)));
+        assert_eq!(b.union(&Interval::new(9, 9)), None);
+        assert_eq!(
+            a.difference(&b),
+            vec![Interval::new(2, 2), Interval::new(8, 8)]
+        );
+        assert_eq!(b.difference(&a), vec![]);
+        assert_eq!(a.difference(&c), vec![Interval::new(2, 5)]);
+    }
+
+    #[test]
+    fn merge_test() {
+        let merged = Interval::merge([
+            Interval::new(12, 12),
+            Interval::new(2, 4),
+            Interval::new(5, 6),
+            Interval::new(-3, 0),
+            Interval::new(3, 10),
+        ]);
+        assert_eq!(
+            merged,
+            vec![
+                Interval::new(-3, 0),
+                Interval::new(2, 10),
+                Interval::new(12, 12)
+            ]
+        );
+        assert!(Interval::merge(vec![]).is_empty());
+    }
+}
@@ -1,9 +1,11 @@
-use std::{
-    error::Error,
-    fs::{self, File},
-    io::{BufRead, BufReader},
-};
+use std::{any::Any, fmt, io::BufRead};
 
+pub use answer::Answer;
+pub use error::AocError;
+pub use input::InputSource;
+
+mod answer;
+pub mod bench;
 mod day1;
 mod day10;
 mod day11;
@@ -19,34 +21,212 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
+mod error;
+pub mod fetch;
+pub mod geom;
+pub mod grid;
+mod input;
+pub mod interval;
+mod pool;
+pub mod run;
+pub mod scaffold;
+pub mod search;
+pub mod verify;
+pub mod watch;
 
 #[macro_use]
 extern crate pest_derive;
 
-fn read_lines(input_path: &str) -> Result<Vec<String>, std::io::Error> {
-    let input_file = File::open(input_path)?;
-    let lines = BufReader::new(input_file).lines();
-
-    lines.collect()
-}
-
-pub fn print_result(aoc_day: &String, input_path: &String) -> Result<(), Box<dyn Error>> {
-    match aoc_day.as_str() {
-        "day1"  => day1::result(read_lines(input_path)?),
-        "day2"  => day2::result(read_lines(input_path)?),
-        "day3"  => day3::result(read_lines(input_path)?),
-        "day4"  => day4::result(read_lines(input_path)?),
-        "day5"  => day5::result(read_lines(input_path)?),
-        "day6"  => day6::result(read_lines(input_path)?),
-        "day7"  => day7::result(fs::read_to_string(input_path)?),
-        "day8"  => day8::result(fs::read_to_string(input_path)?),
-        "day9"  => day9::result(read_lines(input_path)?),
-        "day10" => day10::result(read_lines(input_path)?),
-        "day11" => day11::result(fs::read_to_string(input_path)?),        
-        "day12" => day12::result(fs::read_to_string(input_path)?),
-        "day13" => day13::result(read_lines(input_path)?),
-        "day14" => day14::result(read_lines(input_path)?),
-        "day15" => day15::result(read_lines(input_path)?),
-        _d => Err(format!("Not implemented Advent Of Code Day selected: {}, currently only [day1,day2] are supported ", aoc_day).into()),        
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A single Advent of Code puzzle, the input is parsed once and then shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError>;
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError>;
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError>;
+
+    /// Overrides a puzzle parameter, i.e. day15's scanned row which differs between the example and the real input.
+    fn configure(&mut self, option: &str, _value: &str) -> Result<(), AocError> {
+        Err(AocError::invalid_option(option, "unknown option"))
+    }
+
+    /// Solves both parts reading the input one line at a time, for inputs too large to load whole.
+    /// Days that need the whole input in memory keep the default, which refuses.
+    fn solve_streaming(&self, _input: &mut dyn BufRead) -> Result<Vec<(Part, Answer)>, AocError> {
+        Err("streaming input is not supported".into())
+    }
+}
+
+/// Object safe view of a [`Solution`], so every day can live in the same registry.
+pub trait Runner {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError>;
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer, AocError>;
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), AocError>;
+
+    fn solve_streaming(&self, input: &mut dyn BufRead) -> Result<Vec<(Part, Answer)>, AocError>;
+}
+
+impl<S> Runner for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer, AocError> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .ok_or("parsed input was produced by a different solution")?;
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), AocError> {
+        Solution::configure(self, option, value)
+    }
+
+    fn solve_streaming(&self, input: &mut dyn BufRead) -> Result<Vec<(Part, Answer)>, AocError> {
+        Solution::solve_streaming(self, input)
+    }
+}
+
+/// Registered day, tags every error coming out of the wrapped solution with the day's name.
+struct DayRunner {
+    day: &'static str,
+    runner: Box<dyn Runner>,
+}
+
+impl Runner for DayRunner {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError> {
+        self.runner.parse(input).map_err(|e| e.in_day(self.day))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer, AocError> {
+        self.runner
+            .solve(input, part)
+            .map_err(|e| e.in_day(self.day))
+    }
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), AocError> {
+        self.runner
+            .configure(option, value)
+            .map_err(|e| e.in_day(self.day))
     }
+
+    fn solve_streaming(&self, input: &mut dyn BufRead) -> Result<Vec<(Part, Answer)>, AocError> {
+        self.runner
+            .solve_streaming(input)
+            .map_err(|e| e.in_day(self.day))
+    }
+}
+
+type NewSolution = fn() -> Box<dyn Runner>;
+
+const SOLUTIONS: &[(&str, NewSolution)] = &[
+    ("day1", || Box::new(day1::Day1::default())),
+    ("day2", || Box::new(day2::Day2::default())),
+    ("day3", || Box::new(day3::Day3::default())),
+    ("day4", || Box::new(day4::Day4::default())),
+    ("day5", || Box::new(day5::Day5)),
+    ("day6", || Box::new(day6::Day6)),
+    ("day7", || Box::new(day7::Day7)),
+    ("day8", || Box::new(day8::Day8)),
+    ("day9", || Box::new(day9::Day9)),
+    ("day10", || Box::new(day10::Day10)),
+    ("day11", || Box::new(day11::Day11)),
+    ("day12", || Box::new(day12::Day12)),
+    ("day13", || Box::new(day13::Day13)),
+    ("day14", || Box::new(day14::Day14)),
+    ("day15", || Box::new(day15::Day15::default())),
+];
+
+/// Names of all registered days, in puzzle order.
+pub fn days() -> impl Iterator<Item = &'static str> {
+    SOLUTIONS.iter().map(|(day, _)| *day)
+}
+
+pub fn solution(aoc_day: &str) -> Option<Box<dyn Runner>> {
+    SOLUTIONS.iter().find(|(day, _)| *day == aoc_day).map(
+        |(day, new_solution)| -> Box<dyn Runner> {
+            Box::new(DayRunner {
+                day,
+                runner: new_solution(),
+            })
+        },
+    )
+}
+
+/// Looks up a day and applies `key=value` options to it, see [`Solution::configure`].
+pub fn configured_solution(
+    aoc_day: &str,
+    options: &[(String, String)],
+) -> Result<Box<dyn Runner>, AocError> {
+    let mut solution =
+        solution(aoc_day).ok_or_else(|| AocError::UnknownDay(aoc_day.to_string()))?;
+    for (option, value) in options {
+        solution.configure(option, value)?;
+    }
+    Ok(solution)
+}
+
+/// Splits `key=value` pairs, as passed on the command line or listed in answers files.
+pub fn parse_options<'a>(
+    options: impl Iterator<Item = &'a str>,
+) -> Result<Vec<(String, String)>, String> {
+    options
+        .map(|o| {
+            o.split_once('=')
+                .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                .ok_or(format!("invalid option, expected key=value, got: {}", o))
+        })
+        .collect()
+}
+
+pub fn solve(
+    aoc_day: &str,
+    input: &str,
+    options: &[(String, String)],
+) -> Result<Vec<(Part, Answer)>, AocError> {
+    let solution = configured_solution(aoc_day, options)?;
+    let parsed = solution.parse(input)?;
+    Part::BOTH
+        .iter()
+        .map(|&part| Ok((part, solution.solve(&*parsed, part)?)))
+        .collect()
+}
+
+/// Like [`solve`], reading `input` line by line for days that support it, see [`Solution::solve_streaming`].
+pub fn solve_streaming(
+    aoc_day: &str,
+    input: &mut dyn BufRead,
+    options: &[(String, String)],
+) -> Result<Vec<(Part, Answer)>, AocError> {
+    configured_solution(aoc_day, options)?.solve_streaming(input)
 }
@@ -1,13 +1,444 @@
-use std::{env, error::Error, process};
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process, thread,
+    time::Duration,
+};
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 3 {
-        eprintln!("2 cmd argument required:\n - day of Advent of Code puzzle, in day1, day2,etc format\n - path to the input text file");
+use adv_of_code_2022::{
+    bench, fetch, run, scaffold,
+    verify::{self, Status},
+    watch, Answer, InputSource,
+};
+
+const USAGE: &str = "usage:
+ adv-of-code-2022 <day> <input> [options]  - solve both parts of a day, i.e. day1 inputs/day1/input.txt, use - to read stdin
+ adv-of-code-2022 <day> --input-str <str>  - solve both parts of a day for an inline input, \\n starts a new line
+ adv-of-code-2022 all [inputs]             - solve every day using <inputs>/dayN/input.txt, inputs dir defaults to ./inputs
+ adv-of-code-2022 verify [inputs]          - check every day against the answers stored in <inputs>/dayN/answers.txt
+ adv-of-code-2022 fetch <day> [inputs]     - download <inputs>/<day>/input.txt unless it's already there, needs AOC_SESSION or a config file
+ adv-of-code-2022 new <day>                - generate src/<day>.rs, register it and create empty inputs/<day>/ files, existing files are kept
+
+options:
+ --bench N          - parse and solve the day N times, reporting min/median/max timings
+ --format F         - text (default) or json, json prints one object per part with day, part, answer, timings and error
+ --group-size N     - day3's elves per group sharing a badge, defaults to 3, same as --opt group_size=N
+ --opt key=value    - set a puzzle parameter, i.e. --opt row=10 for day15's example or --opt report=stats for day1, can be repeated
+ --threads N        - number of worker threads for all, defaults to the number of CPUs
+ --watch            - re-run the day whenever its input file changes, showing how the answers changed
+ --stream           - read the input line by line instead of loading it whole, for huge inputs, only day1 supports it
+ --answers FILE     - with --watch, also check answers against FILE, in the inputs/dayN/answers.txt format";
+
+#[derive(Debug, Default, PartialEq)]
+enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Default)]
+struct Args {
+    positional: Vec<String>,
+    bench: Option<usize>,
+    format: Format,
+    options: Vec<(String, String)>,
+    input_str: Option<String>,
+    watch: bool,
+    stream: bool,
+    threads: Option<usize>,
+    answers: Option<PathBuf>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+        let mut parsed = Args::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--bench" => {
+                    let runs = args.next().ok_or("--bench requires number of runs")?;
+                    let runs = runs
+                        .parse::<usize>()
+                        .map_err(|e| format!("invalid --bench runs: {}, {}", runs, e))?;
+                    parsed.bench = Some(runs);
+                }
+                "--format" => {
+                    parsed.format = match args.next().as_deref() {
+                        Some("text") => Format::Text,
+                        Some("json") => Format::Json,
+                        f => {
+                            return Err(format!(
+                                "--format requires text or json, got: {}",
+                                f.unwrap_or_default()
+                            ))
+                        }
+                    };
+                }
+                "--watch" => parsed.watch = true,
+                "--stream" => parsed.stream = true,
+                "--threads" => {
+                    let threads = args.next().ok_or("--threads requires number of threads")?;
+                    let threads =
+                        threads
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|&t| t > 0)
+                            .ok_or(format!(
+                                "invalid --threads: {}, expected a positive number",
+                                threads
+                            ))?;
+                    parsed.threads = Some(threads);
+                }
+                "--answers" => {
+                    parsed.answers = Some(PathBuf::from(
+                        args.next().ok_or("--answers requires a file")?,
+                    ));
+                }
+                "--input-str" => {
+                    parsed.input_str =
+                        Some(args.next().ok_or("--input-str requires the puzzle input")?);
+                }
+                "--opt" => {
+                    let option = args.next().ok_or("--opt requires key=value")?;
+                    parsed
+                        .options
+                        .extend(adv_of_code_2022::parse_options(std::iter::once(
+                            option.as_str(),
+                        ))?);
+                }
+                "--group-size" => {
+                    let size = args
+                        .next()
+                        .ok_or("--group-size requires a number of elves")?;
+                    // day3 validates the size like any other --opt group_size=N
+                    parsed.options.push(("group_size".to_string(), size));
+                }
+                flag if flag.starts_with("--") => return Err(format!("unknown option: {}", flag)),
+                _ => parsed.positional.push(arg),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+fn main() {
+    let args = Args::parse(env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        process::exit(1);
+    });
+    if let Err(e) = run(&args) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let day_input = match (&args.input_str, &args.positional[..]) {
+        (Some(input), [aoc_day]) => Some((aoc_day, InputSource::inline(input))),
+        (None, [aoc_day, input])
+            if !["all", "verify", "fetch", "new"].contains(&aoc_day.as_str()) =>
+        {
+            Some((aoc_day, InputSource::from_arg(input)))
+        }
+        _ => None,
+    };
+    if let Some((aoc_day, source)) = day_input {
+        if args.watch {
+            return match (&source, args.bench, &args.format) {
+                (InputSource::File(path), None, Format::Text) => {
+                    watch_day(aoc_day, path, args.answers.as_deref(), &args.options)
+                }
+                _ => Err("--watch needs an input file and can't be combined with --bench or --format json".into()),
+            };
+        }
+        if args.stream {
+            return match (args.bench, &args.format) {
+                (None, Format::Text) => print_day_streaming(aoc_day, &source, &args.options),
+                _ => Err("--stream can't be combined with --bench or --format json".into()),
+            };
+        }
+        return match (args.bench, &args.format) {
+            (Some(_), Format::Json) => {
+                Err("--format json is not supported together with --bench".into())
+            }
+            (Some(runs), Format::Text) => print_bench(aoc_day, &source, runs, &args.options),
+            (None, Format::Text) => print_day(aoc_day, &source, &args.options),
+            (None, Format::Json) => print_day_json(aoc_day, &source, &args.options),
+        };
+    }
+
+    match args
+        .positional
+        .iter()
+        .map(|a| a.as_str())
+        .collect::<Vec<&str>>()[..]
+    {
+        ["all"] => run_all("inputs", args),
+        ["all", inputs_dir] => run_all(inputs_dir, args),
+        ["verify"] => run_verify("inputs"),
+        ["verify", inputs_dir] => run_verify(inputs_dir),
+        ["fetch", aoc_day] => run_fetch(aoc_day, "inputs"),
+        ["fetch", aoc_day, inputs_dir] => run_fetch(aoc_day, inputs_dir),
+        ["new", aoc_day] => {
+            for step in scaffold::new_day(Path::new("."), aoc_day)? {
+                println!("{}", step);
+            }
+            Ok(())
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    }
+}
+
+fn run_all(inputs_dir: &str, args: &Args) -> Result<(), Box<dyn Error>> {
+    let threads = args
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let reports = run::run_all(Path::new(inputs_dir), threads);
+    match args.format {
+        Format::Text => print_table(&reports),
+        Format::Json => println!("{}", run::to_json(&reports)),
+    }
+    Ok(())
+}
+
+fn run_fetch(aoc_day: &str, inputs_dir: &str) -> Result<(), Box<dyn Error>> {
+    let config = fetch::FetchConfig::load()?;
+    match fetch::fetch(&fetch::Curl, &config, Path::new(inputs_dir), aoc_day)? {
+        fetch::Fetched::Cached(path) => println!(
+            "{} already exists, not downloading it again",
+            path.display()
+        ),
+        fetch::Fetched::Downloaded(path) => println!("downloaded {}", path.display()),
+    }
+    Ok(())
+}
+
+fn run_verify(inputs_dir: &str) -> Result<(), Box<dyn Error>> {
+    let verifications = verify::verify_all(Path::new(inputs_dir));
+    for v in &verifications {
+        let status = match &v.status {
+            Status::Pass => "PASS",
+            Status::Fail { .. } => "FAIL",
+            Status::Missing => "MISSING",
+        };
+        println!("{:<7} {:<5} {:<16} part {}", status, v.day, v.input, v.part);
+        if let Status::Fail { expected, actual } = &v.status {
+            println!(
+                "    expected: {}",
+                expected.replace('\n', "\n              ")
+            );
+            println!("    actual:   {}", actual.replace('\n', "\n              "));
+        }
+    }
+
+    let failed = verifications
+        .iter()
+        .filter(|v| matches!(v.status, Status::Fail { .. }))
+        .count();
+    if failed > 0 {
+        eprintln!("{} of {} checks failed", failed, verifications.len());
         process::exit(1);
     }
-    let aoc_day = &args[1];
-    let input_path = &args[2];
+    Ok(())
+}
+
+fn read_input(source: &InputSource) -> Result<String, Box<dyn Error>> {
+    source
+        .read()
+        .map_err(|e| format!("failed to read input from {}: {}", source, e).into())
+}
+
+fn print_day(
+    aoc_day: &str,
+    source: &InputSource,
+    options: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    let input = read_input(source)?;
+    for (part, answer) in adv_of_code_2022::solve(aoc_day, &input, options)? {
+        match answer {
+            Answer::Multiline(_) => println!("Part {}:\n{}", part, answer),
+            _ => println!("Part {}: {}", part, answer),
+        }
+    }
+    Ok(())
+}
+
+fn print_day_streaming(
+    aoc_day: &str,
+    source: &InputSource,
+    options: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    let mut reader = source
+        .reader()
+        .map_err(|e| format!("failed to read input from {}: {}", source, e))?;
+    for (part, answer) in adv_of_code_2022::solve_streaming(aoc_day, &mut reader, options)? {
+        println!("Part {}: {}", part, answer);
+    }
+    Ok(())
+}
+
+// errors are part of the JSON output, the exit code still tells scripts whether the day was solved
+fn print_day_json(
+    aoc_day: &str,
+    source: &InputSource,
+    options: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    let reports = match read_input(source) {
+        Ok(input) => run::run_day(aoc_day, &input, options),
+        Err(e) => run::failed_day(aoc_day, &e.to_string()),
+    };
+    println!("{}", run::to_json(&reports));
+    if reports.iter().any(|r| r.answer.is_err()) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+fn watch_day(
+    aoc_day: &str,
+    input: &Path,
+    answers: Option<&Path>,
+    options: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    let mut watched = vec![input.to_path_buf()];
+    watched.extend(answers.map(Path::to_path_buf));
+    let mut watcher = watch::Watcher::new(watched);
+    let input_name = watch::input_name(input);
+    let mut previous: Option<Vec<run::PartReport>> = None;
+
+    println!("watching {}, press Ctrl-C to stop", input.display());
+    loop {
+        if watcher.changed() {
+            let expected = match answers.map(fs::read_to_string) {
+                Some(Ok(text)) => verify::Answers::parse(&text)
+                    .map_err(|e| eprintln!("ignoring invalid answers file: {}", e))
+                    .ok(),
+                Some(Err(e)) => {
+                    eprintln!("ignoring unreadable answers file: {}", e);
+                    None
+                }
+                None => None,
+            };
+            // options recorded for this input in the answers file, command line ones win
+            let mut run_options = expected
+                .as_ref()
+                .map_or(vec![], |a| a.options(&input_name).to_vec());
+            run_options.extend_from_slice(options);
+
+            let reports = match fs::read_to_string(input) {
+                Ok(text) => run::run_day(aoc_day, &text, &run_options),
+                Err(e) => run::failed_day(
+                    aoc_day,
+                    &format!("failed to read input from {}: {}", input.display(), e),
+                ),
+            };
+            println!("--- {} {}", aoc_day, input.display());
+            for line in watch::describe_changes(
+                previous.as_deref(),
+                &reports,
+                expected.as_ref(),
+                &input_name,
+            ) {
+                println!("{}", line);
+            }
+            previous = Some(reports);
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+fn print_bench(
+    aoc_day: &str,
+    source: &InputSource,
+    runs: usize,
+    options: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    let input = read_input(source)?;
+    let report = bench::bench(aoc_day, &input, runs, options)?;
+
+    println!("{} - {} runs", report.day, report.runs);
+    println!("{:<8} {:>12} {:>12} {:>12}", "", "min", "median", "max");
+    let print_timings = |name: String, t: &bench::Timings| {
+        println!(
+            "{:<8} {:>12} {:>12} {:>12}",
+            name,
+            format_duration(t.min),
+            format_duration(t.median),
+            format_duration(t.max)
+        )
+    };
+    print_timings("parse".to_string(), &report.parse);
+    for (part, timings) in &report.parts {
+        print_timings(format!("part {}", part), timings);
+    }
+    Ok(())
+}
+
+fn print_table(reports: &[run::PartReport]) {
+    let rows: Vec<[String; 5]> = reports
+        .iter()
+        .map(|r| {
+            let answer = match &r.answer {
+                Ok(a) => a.to_string(),
+                Err(e) => format!("ERROR: {}", e),
+            };
+            [
+                r.day.clone(),
+                r.part.to_string(),
+                answer,
+                format_duration(r.parse_time),
+                format_duration(r.solve_time),
+            ]
+        })
+        .collect();
+    let header = ["Day", "Part", "Answer", "Parse", "Elapsed"].map(|h| h.to_string());
+    let widths: Vec<usize> = (0..header.len())
+        .map(|col| {
+            rows.iter()
+                .chain(std::iter::once(&header))
+                .flat_map(|r| r[col].lines())
+                .map(|l| l.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let print_row = |row: &[String; 5]| {
+        // multi line answers (day10's CRT) continue on following lines within the Answer column
+        let mut answer_lines: Vec<&str> = row[2].lines().collect();
+        if answer_lines.is_empty() {
+            answer_lines.push("");
+        }
+        for (i, answer_line) in answer_lines.iter().enumerate() {
+            let cells: Vec<String> = (0..row.len())
+                .map(|col| match col {
+                    2 => format!("{:<w$}", answer_line, w = widths[col]),
+                    _ if i == 0 => format!("{:<w$}", row[col], w = widths[col]),
+                    _ => " ".repeat(widths[col]),
+                })
+                .collect();
+            println!("| {} |", cells.join(" | "));
+        }
+    };
+
+    print_row(&header);
+    println!(
+        "|{}|",
+        widths
+            .iter()
+            .map(|w| "-".repeat(w + 2))
+            .collect::<Vec<String>>()
+            .join("|")
+    );
+    rows.iter().for_each(print_row);
+}
 
-    adv_of_code_2022::print_result(aoc_day, input_path)
+fn format_duration(d: Duration) -> String {
+    format!("{:.2?}", d)
 }
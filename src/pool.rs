@@ -0,0 +1,77 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::Mutex,
+    thread,
+};
+
+/// Runs `jobs` on up to `threads` worker threads, results come back in the order of `jobs`.
+///
+/// A panicking job doesn't take the other jobs down, its panic message is returned as that job's error.
+pub fn run_jobs<T, F>(jobs: Vec<F>, threads: usize) -> Vec<Result<T, String>>
+where
+    F: FnOnce() -> T + Send,
+    T: Send,
+{
+    let job_count = jobs.len();
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let results: Mutex<Vec<Option<Result<T, String>>>> =
+        Mutex::new((0..job_count).map(|_| None).collect());
+
+    thread::scope(|s| {
+        for _ in 0..threads.clamp(1, job_count.max(1)) {
+            s.spawn(|| loop {
+                // the lock is released before the job runs, so the other workers can pick up the next jobs
+                let next = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                let Some((i, job)) = next else {
+                    break;
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(job)).map_err(panic_message);
+                results.lock().unwrap_or_else(|e| e.into_inner())[i] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .map(|r| r.unwrap_or_else(|| Err("job was never run".to_string())))
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string());
+    format!("panicked: {}", message)
+}
+
+#[cfg(test)]
+mod pool_test {
+    use super::*;
+
+    #[test]
+    fn run_jobs_test() {
+        let jobs: Vec<Box<dyn FnOnce() -> usize + Send>> = (0..20)
+            .map(|i| -> Box<dyn FnOnce() -> usize + Send> {
+                match i {
+                    7 => Box::new(|| panic!("job 7 failed")),
+                    i => Box::new(move || i * i),
+                }
+            })
+            .collect();
+
+        let results = run_jobs(jobs, 4);
+        assert_eq!(results.len(), 20);
+        for (i, r) in results.iter().enumerate() {
+            match i {
+                7 => assert_eq!(r, &Err("panicked: job 7 failed".to_string())),
+                i => assert_eq!(r, &Ok(i * i)),
+            }
+        }
+        assert!(run_jobs(Vec::<fn() -> u8>::new(), 0).is_empty());
+    }
+}
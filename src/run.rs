@@ -0,0 +1,229 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{configured_solution, days, pool, Answer, Part};
+
+/// Outcome of solving a single part of a day, together with how long it took.
+#[derive(Debug)]
+pub struct PartReport {
+    pub day: String,
+    pub part: Part,
+    pub answer: Result<Answer, String>,
+    // parsing is shared by both parts of a day, so both reports carry the same parse_time
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+impl PartReport {
+    fn failed(day: &str, part: Part, error: String, parse_time: Duration) -> PartReport {
+        PartReport {
+            day: day.to_string(),
+            part,
+            answer: Err(error),
+            parse_time,
+            solve_time: Duration::ZERO,
+        }
+    }
+
+    /// Single line JSON object, answers are always strings and timings are whole nanoseconds so the shape never varies by day:
+    /// `{"day":"day1","part":1,"answer":"24000","timings":{"parse_ns":1200,"solve_ns":300},"error":null}`
+    pub fn to_json(&self) -> String {
+        let (answer, error) = match &self.answer {
+            Ok(a) => (json_string(&a.to_string()), "null".to_string()),
+            Err(e) => ("null".to_string(), json_string(e)),
+        };
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"timings\":{{\"parse_ns\":{},\"solve_ns\":{}}},\"error\":{}}}",
+            json_string(&self.day),
+            self.part,
+            answer,
+            self.parse_time.as_nanos(),
+            self.solve_time.as_nanos(),
+            error
+        )
+    }
+}
+
+/// JSON array of reports, one report per line.
+pub fn to_json(reports: &[PartReport]) -> String {
+    let objects: Vec<String> = reports
+        .iter()
+        .map(|r| format!("  {}", r.to_json()))
+        .collect();
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Both parts of a day failing with the same error, i.e. when its input can't be read.
+pub fn failed_day(aoc_day: &str, error: &str) -> Vec<PartReport> {
+    Part::BOTH
+        .iter()
+        .map(|&part| PartReport::failed(aoc_day, part, error.to_string(), Duration::ZERO))
+        .collect()
+}
+
+/// Location of a day's puzzle input in the `inputs/dayN/input.txt` layout.
+pub fn input_path(inputs_dir: &Path, aoc_day: &str) -> PathBuf {
+    inputs_dir.join(aoc_day).join("input.txt")
+}
+
+pub fn run_day(aoc_day: &str, input: &str, options: &[(String, String)]) -> Vec<PartReport> {
+    run_parts(aoc_day, input, &Part::BOTH, options)
+}
+
+/// Parses `input` once and solves the given `parts` with it.
+pub fn run_parts(
+    aoc_day: &str,
+    input: &str,
+    parts: &[Part],
+    options: &[(String, String)],
+) -> Vec<PartReport> {
+    let solution = match configured_solution(aoc_day, options) {
+        Ok(s) => s,
+        Err(e) => {
+            return parts
+                .iter()
+                .map(|&part| PartReport::failed(aoc_day, part, e.to_string(), Duration::ZERO))
+                .collect()
+        }
+    };
+
+    let parse_start = Instant::now();
+    let parsed = solution.parse(input);
+    let parse_time = parse_start.elapsed();
+
+    parts
+        .iter()
+        .map(|&part| match &parsed {
+            Ok(parsed) => {
+                let solve_start = Instant::now();
+                let answer = solution.solve(&**parsed, part).map_err(|e| e.to_string());
+                PartReport {
+                    day: aoc_day.to_string(),
+                    part,
+                    answer,
+                    parse_time,
+                    solve_time: solve_start.elapsed(),
+                }
+            }
+            Err(e) => PartReport::failed(aoc_day, part, format!("parse error: {}", e), parse_time),
+        })
+        .collect()
+}
+
+/// Runs every registered day against its input under `inputs_dir`, a missing input only fails that day's rows.
+///
+/// Every day/part is a separate job on one of `threads` workers, so each part parses the input on its own
+/// and reports its own parse time. Reports come back in day order, a panicking part is reported as its error.
+pub fn run_all(inputs_dir: &Path, threads: usize) -> Vec<PartReport> {
+    let inputs: Vec<(&str, Result<String, String>)> = days()
+        .map(|aoc_day| {
+            let path = input_path(inputs_dir, aoc_day);
+            let input = fs::read_to_string(&path)
+                .map_err(|e| format!("missing input {}: {}", path.display(), e));
+            (aoc_day, input)
+        })
+        .collect();
+    let jobs: Vec<(&str, &Result<String, String>, Part)> = inputs
+        .iter()
+        .flat_map(|(aoc_day, input)| Part::BOTH.iter().map(move |&part| (*aoc_day, input, part)))
+        .collect();
+
+    let reports = pool::run_jobs(
+        jobs.iter()
+            .map(|&(aoc_day, input, part)| {
+                move || match input {
+                    Ok(input) => run_parts(aoc_day, input, &[part], &[]).remove(0),
+                    Err(e) => PartReport::failed(aoc_day, part, e.clone(), Duration::ZERO),
+                }
+            })
+            .collect(),
+        threads,
+    );
+    reports
+        .into_iter()
+        .zip(&jobs)
+        .map(|(report, &(aoc_day, _, part))| {
+            report.unwrap_or_else(|e| PartReport::failed(aoc_day, part, e, Duration::ZERO))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod run_test {
+    use super::*;
+
+    #[test]
+    fn json_report_test() {
+        let reports = vec![
+            PartReport {
+                day: "day10".to_string(),
+                part: Part::Two,
+                answer: Ok(Answer::Multiline("#.\n\"#\"\n".to_string())),
+                parse_time: Duration::from_nanos(1500),
+                solve_time: Duration::from_nanos(20),
+            },
+            PartReport::failed(
+                "day1",
+                Part::One,
+                "parse error: bad\tline".to_string(),
+                Duration::ZERO,
+            ),
+        ];
+        assert_eq!(
+            to_json(&reports),
+            r##"[
+  {"day":"day10","part":2,"answer":"#.\n\"#\"","timings":{"parse_ns":1500,"solve_ns":20},"error":null},
+  {"day":"day1","part":1,"answer":null,"timings":{"parse_ns":0,"solve_ns":0},"error":"parse error: bad\tline"}
+]"##
+        );
+    }
+
+    #[test]
+    fn run_all_missing_input_test() {
+        let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+        let inputs_dir = std::env::temp_dir().join(format!("aoc-run-all-{}", std::process::id()));
+        for aoc_day in days().filter(|&d| d != "day7") {
+            fs::create_dir_all(inputs_dir.join(aoc_day)).unwrap();
+            fs::copy(
+                examples.join(aoc_day).join("test_input.txt"),
+                input_path(&inputs_dir, aoc_day),
+            )
+            .unwrap();
+        }
+
+        let reports = run_all(&inputs_dir, 4);
+        fs::remove_dir_all(&inputs_dir).unwrap();
+        assert_eq!(reports.len(), days().count() * 2);
+        for r in &reports {
+            if r.day == "day7" {
+                let error = r.answer.as_ref().unwrap_err();
+                assert!(error.starts_with("missing input"), "{}", error);
+            } else {
+                // other days run, though a freshly scaffolded one still fails as not solved yet
+                let missing = matches!(&r.answer, Err(e) if e.starts_with("missing input"));
+                assert!(!missing, "{} {}: {:?}", r.day, r.part, r.answer);
+            }
+        }
+    }
+}
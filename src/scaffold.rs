@@ -0,0 +1,259 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Generates the skeleton of a new day under `root`, the repository's top level directory.
+///
+/// Writes `src/dayN.rs` from a template unless the day already has a module, i.e. `src/dayN/mod.rs`, registers it in `src/lib.rs` and creates `inputs/dayN/` with empty
+/// input, example and answers files. Steps which were already done are skipped, so running it twice is harmless,
+/// and existing files are never overwritten. Returns a description of every step for the caller to print.
+pub fn new_day(root: &Path, aoc_day: &str) -> io::Result<Vec<String>> {
+    let day_no = aoc_day
+        .strip_prefix("day")
+        .and_then(|n| n.parse::<u32>().ok())
+        .filter(|n| (1..=25).contains(n))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid day: {}, expected day1 .. day25", aoc_day),
+            )
+        })?;
+
+    let mut steps = vec![];
+    let src = root.join("src");
+    let lib_rs = src.join("lib.rs");
+    let dir_module = src.join(aoc_day).join("mod.rs");
+    // a second module file for the day would stop the crate from compiling
+    if dir_module.exists() {
+        steps.push(format!(
+            "skipped {}, it already exists",
+            dir_module.display()
+        ));
+    } else if declares_mod(&fs::read_to_string(&lib_rs)?, aoc_day) {
+        steps.push(format!(
+            "skipped creating {}, {} already declares it",
+            aoc_day,
+            lib_rs.display()
+        ));
+    } else {
+        let module = src.join(format!("{}.rs", aoc_day));
+        steps.push(create_file(&module, &module_template(aoc_day, day_no))?);
+    }
+    steps.push(register(&lib_rs, aoc_day, day_no)?);
+
+    let inputs = root.join("inputs").join(aoc_day);
+    fs::create_dir_all(&inputs)?;
+    for file in ["input.txt", "test_input.txt", "answers.txt"] {
+        steps.push(create_file(&inputs.join(file), "")?);
+    }
+    Ok(steps)
+}
+
+fn create_file(path: &Path, content: &str) -> io::Result<String> {
+    // create_new fails on existing files, so a file written in the meantime is not clobbered either
+    match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+    {
+        Ok(mut file) => {
+            file.write_all(content.as_bytes())?;
+            Ok(format!("created {}", path.display()))
+        }
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            Ok(format!("skipped {}, it already exists", path.display()))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// adds `mod dayN;` and the SOLUTIONS entry to lib.rs, keeping both lists in their current order
+fn register(lib_rs: &Path, aoc_day: &str, day_no: u32) -> io::Result<String> {
+    let source = fs::read_to_string(lib_rs)?;
+    let mut lines: Vec<String> = source.lines().map(|l| l.to_string()).collect();
+    let mod_line = format!("mod {};", aoc_day);
+    let entry_line = format!(
+        "    (\"{}\", || Box::new({}::Day{})),",
+        aoc_day, aoc_day, day_no
+    );
+
+    let has_mod = declares_mod(&source, aoc_day);
+    let has_entry = lines
+        .iter()
+        .any(|l| l.trim_start().starts_with(&format!("(\"{}\",", aoc_day)));
+    if has_mod && has_entry {
+        return Ok(format!(
+            "skipped registering {}, {} already has it",
+            aoc_day,
+            lib_rs.display()
+        ));
+    }
+
+    if !has_entry {
+        let start = lines
+            .iter()
+            .position(|l| l.starts_with("const SOLUTIONS"))
+            .ok_or_else(|| invalid_lib_rs(lib_rs, "missing const SOLUTIONS"))?;
+        let end = lines[start..]
+            .iter()
+            .position(|l| l == "];")
+            .map(|i| start + i)
+            .ok_or_else(|| invalid_lib_rs(lib_rs, "SOLUTIONS is not closed by ];"))?;
+        let at = (start + 1..end)
+            .find(|&i| registered_day_no(&lines[i]).is_some_and(|n| n > day_no))
+            .unwrap_or(end);
+        lines.insert(at, entry_line);
+    }
+
+    if !has_mod {
+        let day_mods: Vec<usize> = (0..lines.len())
+            .filter(|&i| lines[i].starts_with("mod day"))
+            .collect();
+        let last = *day_mods
+            .last()
+            .ok_or_else(|| invalid_lib_rs(lib_rs, "no mod dayN; declarations"))?;
+        let at = day_mods
+            .into_iter()
+            .find(|&i| lines[i].trim_start_matches("mod ").trim_end_matches(';') > aoc_day)
+            .unwrap_or(last + 1);
+        lines.insert(at, mod_line);
+    }
+
+    fs::write(lib_rs, lines.join("\n") + "\n")?;
+    Ok(format!("registered {} in {}", aoc_day, lib_rs.display()))
+}
+
+fn declares_mod(lib_rs_source: &str, aoc_day: &str) -> bool {
+    let mod_line = format!("mod {};", aoc_day);
+    lib_rs_source
+        .lines()
+        .any(|l| l.trim_start_matches("pub ") == mod_line)
+}
+
+fn registered_day_no(line: &str) -> Option<u32> {
+    line.trim_start()
+        .strip_prefix("(\"day")?
+        .split('"')
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn invalid_lib_rs(lib_rs: &Path, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "can't register the new day in {}: {}",
+            lib_rs.display(),
+            message
+        ),
+    )
+}
+
+fn module_template(aoc_day: &str, day_no: u32) -> String {
+    MODULE_TEMPLATE
+        .replace("{day}", aoc_day)
+        .replace("{Day}", &format!("Day{}", day_no))
+}
+
+const MODULE_TEMPLATE: &str = r#"use crate::{error::parse_lines, Answer, AocError, Solution};
+
+pub struct {Day};
+
+impl Solution for {Day} {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, AocError> {
+        parse_lines(input, |l| Ok(l.to_string()))
+    }
+
+    fn part1(&self, _input: &Vec<String>) -> Result<Answer, AocError> {
+        Err("{day} part 1 is not solved yet".into())
+    }
+
+    fn part2(&self, _input: &Vec<String>) -> Result<Answer, AocError> {
+        Err("{day} part 2 is not solved yet".into())
+    }
+}
+
+#[cfg(test)]
+mod {day}_test {
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    fn parse_example_test() {
+        assert!({Day}.parse(EXAMPLE).is_ok());
+    }
+}
+"#;
+
+#[cfg(test)]
+mod scaffold_test {
+    use super::*;
+
+    #[test]
+    fn new_day_test() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        let lib_rs = "mod day1;
+mod day2;
+mod error;
+
+const SOLUTIONS: &[(&str, NewSolution)] = &[
+    (\"day1\", || Box::new(day1::Day1)),
+    (\"day2\", || Box::new(day2::Day2)),
+];
+";
+        fs::write(root.join("src/lib.rs"), lib_rs).unwrap();
+        fs::write(root.join("src/day2.rs"), "// hand written").unwrap();
+        fs::create_dir_all(root.join("src/day11")).unwrap();
+        fs::write(root.join("src/day11/mod.rs"), "// hand written").unwrap();
+
+        new_day(&root, "day16").unwrap();
+        let steps = new_day(&root, "day2").unwrap();
+        assert!(steps[0].starts_with("skipped"));
+        // directory modules count as existing too
+        let steps = new_day(&root, "day11").unwrap();
+        assert!(steps[0].starts_with("skipped"), "{:?}", steps);
+        assert!(!root.join("src/day11.rs").exists());
+        // second run changes nothing
+        let steps = new_day(&root, "day16").unwrap();
+        assert!(
+            steps.iter().all(|s| s.starts_with("skipped")),
+            "{:?}",
+            steps
+        );
+
+        assert_eq!(
+            fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "mod day1;
+mod day11;
+mod day16;
+mod day2;
+mod error;
+
+const SOLUTIONS: &[(&str, NewSolution)] = &[
+    (\"day1\", || Box::new(day1::Day1)),
+    (\"day2\", || Box::new(day2::Day2)),
+    (\"day11\", || Box::new(day11::Day11)),
+    (\"day16\", || Box::new(day16::Day16)),
+];
+"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/day2.rs")).unwrap(),
+            "// hand written"
+        );
+        assert!(fs::read_to_string(root.join("src/day16.rs"))
+            .unwrap()
+            .contains("impl Solution for Day16"));
+        assert!(root.join("inputs/day16/test_input.txt").exists());
+        assert!(new_day(&root, "day26").is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
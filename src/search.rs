@@ -0,0 +1,227 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Cheapest way found from one of the starts to a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: usize,
+    /// Every node visited, from the start up to and including the goal.
+    pub nodes: Vec<N>,
+}
+
+// nodes seen so far, numbered in the order they were found so parents are tracked by index
+struct Visited<N> {
+    index: HashMap<N, usize>,
+    nodes: Vec<(N, Option<usize>)>,
+}
+
+impl<N: Eq + Hash + Clone> Visited<N> {
+    fn new() -> Visited<N> {
+        Visited {
+            index: HashMap::new(),
+            nodes: vec![],
+        }
+    }
+
+    // None if the node was seen before
+    fn insert(&mut self, node: N, parent: Option<usize>) -> Option<usize> {
+        match self.index.entry(node.clone()) {
+            Entry::Occupied(_) => None,
+            Entry::Vacant(e) => {
+                e.insert(self.nodes.len());
+                self.nodes.push((node, parent));
+                Some(self.nodes.len() - 1)
+            }
+        }
+    }
+
+    fn path(&self, mut i: usize, cost: usize) -> Path<N> {
+        let mut nodes = vec![self.nodes[i].0.clone()];
+        while let Some(parent) = self.nodes[i].1 {
+            nodes.push(self.nodes[parent].0.clone());
+            i = parent;
+        }
+        nodes.reverse();
+        Path { cost, nodes }
+    }
+}
+
+/// Breadth first search, every step costs 1. Searching from several `starts` at once finds the closest of them.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    let mut to_visit = VecDeque::new();
+    for start in starts {
+        if let Some(i) = visited.insert(start, None) {
+            to_visit.push_back((i, 0));
+        }
+    }
+
+    while let Some((i, cost)) = to_visit.pop_front() {
+        let node = visited.nodes[i].0.clone();
+        if is_goal(&node) {
+            return Some(visited.path(i, cost));
+        }
+        for n in neighbours(&node) {
+            if let Some(n_i) = visited.insert(n, Some(i)) {
+                to_visit.push_back((n_i, cost + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Cheapest path when steps have different costs, `neighbours` returns each neighbour with the cost of stepping onto it.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    astar(starts, neighbours, |_| 0, is_goal)
+}
+
+/// Like [`dijkstra`], exploring nodes in order of cost so far plus `heuristic`'s estimate of the cost left.
+/// The heuristic must never overestimate, otherwise the path found might not be the cheapest.
+/// Finished nodes are re-opened when a cheaper way to them turns up, which only happens for inconsistent heuristics.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> usize,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    // unlike bfs, a node can be reached more cheaply after it was first seen, so parents and costs get updated
+    let mut visited = Visited::new();
+    let mut costs: Vec<usize> = vec![];
+    let mut done: Vec<bool> = vec![];
+    let mut to_visit = BinaryHeap::new();
+
+    for start in starts {
+        let estimate = heuristic(&start);
+        if let Some(i) = visited.insert(start, None) {
+            to_visit.push(Reverse((estimate, 0, i)));
+            costs.push(0);
+            done.push(false);
+        }
+    }
+
+    while let Some(Reverse((_, cost, i))) = to_visit.pop() {
+        if done[i] || cost > costs[i] {
+            // stale entry, the node was already reached more cheaply
+            continue;
+        }
+        done[i] = true;
+        let node = visited.nodes[i].0.clone();
+        if is_goal(&node) {
+            return Some(visited.path(i, cost));
+        }
+
+        for (n, step_cost) in neighbours(&node) {
+            let n_cost = cost + step_cost;
+            let estimate = heuristic(&n);
+            let n_i = match visited.index.get(&n) {
+                Some(&n_i) if n_cost >= costs[n_i] => continue,
+                Some(&n_i) => {
+                    visited.nodes[n_i].1 = Some(i);
+                    costs[n_i] = n_cost;
+                    done[n_i] = false;
+                    n_i
+                }
+                None => {
+                    costs.push(n_cost);
+                    done.push(false);
+                    visited
+                        .insert(n, Some(i))
+                        .expect("node was not seen before")
+                }
+            };
+            to_visit.push(Reverse((n_cost + estimate, n_cost, n_i)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod search_test {
+    use super::*;
+
+    // 0 - 1 costs 7, 0 - 2 - 3 - 1 costs 1 + 1 + 1, 4 is unreachable
+    fn weighted(n: &u32) -> Vec<(u32, usize)> {
+        match n {
+            0 => vec![(1, 7), (2, 1)],
+            2 => vec![(3, 1)],
+            3 => vec![(1, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn search_test() {
+        let unweighted = |n: &u32| weighted(n).into_iter().map(|(n, _)| n);
+        assert_eq!(
+            bfs([0], unweighted, |&n| n == 1),
+            Some(Path {
+                cost: 1,
+                nodes: vec![0, 1]
+            })
+        );
+        assert_eq!(
+            dijkstra([0], weighted, |&n| n == 1),
+            Some(Path {
+                cost: 3,
+                nodes: vec![0, 2, 3, 1]
+            })
+        );
+        assert_eq!(
+            astar([0], weighted, |&n| if n == 1 { 0 } else { 1 }, |&n| n == 1),
+            Some(Path {
+                cost: 3,
+                nodes: vec![0, 2, 3, 1]
+            })
+        );
+        // multi source, 3 is one step away from the goal
+        assert_eq!(
+            bfs([0, 3], unweighted, |&n| n == 1),
+            Some(Path {
+                cost: 1,
+                nodes: vec![0, 1]
+            })
+        );
+        assert_eq!(dijkstra([0], weighted, |&n| n == 4), None);
+    }
+    #[test]
+    fn astar_inconsistent_heuristic_test() {
+        // S=0, A=1, B=2, C=3, G=4, h(A)=4 never overestimates but makes C finish through B first
+        let edges = |n: &u32| match n {
+            0 => vec![(1, 1), (2, 1)],
+            1 => vec![(3, 1)],
+            2 => vec![(3, 2)],
+            3 => vec![(4, 3)],
+            _ => vec![],
+        };
+        assert_eq!(
+            astar([0], edges, |&n| if n == 1 { 4 } else { 0 }, |&n| n == 4),
+            Some(Path {
+                cost: 5,
+                nodes: vec![0, 1, 3, 4]
+            })
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{days, parse_options, run, Part};
+
+/// Expected answers for a day, keyed by input file name and part.
+///
+/// Stored in `inputs/dayN/answers.txt`, one `[input file]` section per input:
+/// ```text
+/// [input.txt]
+/// part1: 14320
+/// part2:
+/// ###...##..
+/// #..#.#..#.
+///
+/// [test_input.txt]
+/// part1: 13140
+/// ```
+/// A `partN:` line with no value starts a multi line answer, which runs until the next blank line.
+/// An `options: key=value, ...` line sets puzzle parameters the section's input needs, see [`crate::Solution::configure`].
+#[derive(Debug, Default)]
+pub struct Answers {
+    inputs: Vec<String>,
+    expected: HashMap<(String, Part), String>,
+    options: HashMap<String, Vec<(String, String)>>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let mut answers = Answers::default();
+        let mut section: Option<String> = None;
+        let mut multiline: Option<(Part, Vec<&str>)> = None;
+
+        for (i, line) in text.lines().enumerate() {
+            if let Some((part, mut lines)) = multiline.take() {
+                if !line.trim().is_empty() {
+                    lines.push(line);
+                    multiline = Some((part, lines));
+                    continue;
+                }
+                answers.insert(&section, part, lines.join("\n"), i)?;
+                continue;
+            }
+
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(input) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                answers.inputs.push(input.to_string());
+                section = Some(input.to_string());
+                continue;
+            }
+            let (key, value) = line.split_once(':').ok_or(format!(
+                "line {}: expected 'partN: answer', got: {}",
+                i + 1,
+                line
+            ))?;
+            let part = match key.trim() {
+                "options" => {
+                    let input = section.as_ref().ok_or(format!(
+                        "line {}: options found before any [input file] section",
+                        i + 1
+                    ))?;
+                    let options = parse_options(value.split(','))
+                        .map_err(|e| format!("line {}: {}", i + 1, e))?;
+                    answers.options.insert(input.clone(), options);
+                    continue;
+                }
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                k => return Err(format!("line {}: unknown part: {}", i + 1, k)),
+            };
+            match value.trim() {
+                "" => multiline = Some((part, vec![])),
+                v => answers.insert(&section, part, v.to_string(), i)?,
+            }
+        }
+        if let Some((part, lines)) = multiline {
+            answers.insert(&section, part, lines.join("\n"), text.lines().count())?;
+        }
+        Ok(answers)
+    }
+
+    fn insert(
+        &mut self,
+        section: &Option<String>,
+        part: Part,
+        answer: String,
+        line: usize,
+    ) -> Result<(), String> {
+        let input = section.as_ref().ok_or(format!(
+            "line {}: answer found before any [input file] section",
+            line + 1
+        ))?;
+        self.expected.insert((input.clone(), part), answer);
+        Ok(())
+    }
+
+    pub fn expected(&self, input: &str, part: Part) -> Option<&String> {
+        self.expected.get(&(input.to_string(), part))
+    }
+
+    pub fn options(&self, input: &str) -> &[(String, String)] {
+        self.options.get(input).map_or(&[], |o| o.as_slice())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub day: String,
+    pub input: String,
+    pub part: Part,
+    pub status: Status,
+}
+
+pub fn answers_path(inputs_dir: &Path, aoc_day: &str) -> PathBuf {
+    inputs_dir.join(aoc_day).join("answers.txt")
+}
+
+/// Checks a day against every input listed in its answers file, plus the standard `input.txt` and `test_input.txt`.
+pub fn verify_day(inputs_dir: &Path, aoc_day: &str) -> Vec<Verification> {
+    verify_inputs(inputs_dir, aoc_day, |_| true)
+}
+
+/// Like [`verify_day`], but only for the puzzle examples, i.e. `test_input.txt` and `test2_input.txt`.
+pub fn verify_examples(inputs_dir: &Path, aoc_day: &str) -> Vec<Verification> {
+    verify_inputs(inputs_dir, aoc_day, |input| input.starts_with("test"))
+}
+
+fn verify_inputs(inputs_dir: &Path, aoc_day: &str, include: fn(&str) -> bool) -> Vec<Verification> {
+    let answers = match fs::read_to_string(answers_path(inputs_dir, aoc_day)) {
+        Ok(text) => Answers::parse(&text),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.to_string()),
+    };
+    let answers = match answers {
+        Ok(answers) => answers,
+        Err(e) => {
+            return Part::BOTH
+                .iter()
+                .map(|&part| Verification {
+                    day: aoc_day.to_string(),
+                    input: "answers.txt".to_string(),
+                    part,
+                    status: Status::Fail {
+                        expected: "valid answers file".to_string(),
+                        actual: e.clone(),
+                    },
+                })
+                .collect();
+        }
+    };
+
+    let mut inputs = vec!["input.txt".to_string(), "test_input.txt".to_string()];
+    inputs.extend(
+        answers
+            .inputs
+            .iter()
+            .filter(|i| !inputs.contains(i))
+            .cloned()
+            .collect::<Vec<String>>(),
+    );
+
+    inputs
+        .iter()
+        .filter(|input| include(input))
+        .flat_map(|input| {
+            // don't bother solving inputs nobody recorded answers for
+            let has_answers = Part::BOTH
+                .iter()
+                .any(|&p| answers.expected(input, p).is_some());
+            let reports = match has_answers {
+                true => fs::read_to_string(inputs_dir.join(aoc_day).join(input))
+                    .map(|text| run::run_day(aoc_day, &text, answers.options(input))),
+                false => Ok(vec![]),
+            };
+            Part::BOTH
+                .iter()
+                .map(|&part| {
+                    let status = match (answers.expected(input, part), &reports) {
+                        (None, _) => Status::Missing,
+                        (Some(expected), Err(e)) => Status::Fail {
+                            expected: expected.clone(),
+                            actual: format!("ERROR: cannot read input, {}", e),
+                        },
+                        (Some(expected), Ok(reports)) => {
+                            let actual =
+                                match reports.iter().find(|r| r.part == part).map(|r| &r.answer) {
+                                    Some(Ok(answer)) => answer.to_string(),
+                                    Some(Err(e)) => format!("ERROR: {}", e),
+                                    None => "ERROR: part was not run".to_string(),
+                                };
+                            if actual.trim() == expected.trim() {
+                                Status::Pass
+                            } else {
+                                Status::Fail {
+                                    expected: expected.clone(),
+                                    actual,
+                                }
+                            }
+                        }
+                    };
+                    Verification {
+                        day: aoc_day.to_string(),
+                        input: input.clone(),
+                        part,
+                        status,
+                    }
+                })
+                .collect::<Vec<Verification>>()
+        })
+        .collect()
+}
+
+pub fn verify_all(inputs_dir: &Path) -> Vec<Verification> {
+    days().flat_map(|d| verify_day(inputs_dir, d)).collect()
+}
+
+#[cfg(test)]
+mod verify_test {
+    use super::*;
+
+    #[test]
+    fn parse_answers_test() {
+        let text = "[input.txt]
+part1: 14320
+part2:
+###..
+#..#.
+
+[test_input.txt]
+part1: 13140";
+
+        let answers = Answers::parse(text).unwrap();
+        assert_eq!(answers.inputs, vec!["input.txt", "test_input.txt"]);
+        assert_eq!(
+            answers.expected("input.txt", Part::One),
+            Some(&"14320".to_string())
+        );
+        assert_eq!(
+            answers.expected("input.txt", Part::Two),
+            Some(&"###..\n#..#.".to_string())
+        );
+        assert_eq!(
+            answers.expected("test_input.txt", Part::One),
+            Some(&"13140".to_string())
+        );
+        assert_eq!(answers.expected("test_input.txt", Part::Two), None);
+    }
+
+    #[test]
+    fn parse_answers_without_section_test() {
+        assert!(Answers::parse("part1: 1").is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{run::PartReport, verify::Answers};
+
+/// Polls files for changes by comparing their modification time and size, which works on any plain filesystem.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    // None until the first poll, so the first call to changed always reports a change
+    stamps: Option<Vec<Option<(SystemTime, u64)>>>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        Watcher {
+            paths,
+            stamps: None,
+        }
+    }
+
+    /// True on the first call and whenever a watched file was modified, created or removed since the previous call.
+    pub fn changed(&mut self) -> bool {
+        let stamps: Vec<Option<(SystemTime, u64)>> = self
+            .paths
+            .iter()
+            .map(|p| {
+                fs::metadata(p)
+                    .ok()
+                    .and_then(|m| Some((m.modified().ok()?, m.len())))
+            })
+            .collect();
+        let changed = self.stamps.as_ref() != Some(&stamps);
+        self.stamps = Some(stamps);
+        changed
+    }
+}
+
+/// One line per part comparing `current` with the `previous` run's answers and, if given, the expected answers
+/// recorded for `input` in an answers file.
+pub fn describe_changes(
+    previous: Option<&[PartReport]>,
+    current: &[PartReport],
+    expected: Option<&Answers>,
+    input: &str,
+) -> Vec<String> {
+    let answer = |r: &PartReport| match &r.answer {
+        Ok(a) => a.to_string(),
+        Err(e) => format!("ERROR: {}", e),
+    };
+    current
+        .iter()
+        .map(|r| {
+            let now = answer(r);
+            let mut line = format!("Part {}: {}", r.part, now.replace('\n', "\n        "));
+            match previous
+                .and_then(|p| p.iter().find(|p| p.part == r.part))
+                .map(answer)
+            {
+                Some(before) if before == now => line.push_str(" (unchanged)"),
+                Some(before) => {
+                    line.push_str(&format!(" (was: {})", before.replace('\n', "\n        ")))
+                }
+                None => {}
+            }
+            match (expected.and_then(|a| a.expected(input, r.part)), &r.answer) {
+                (Some(e), Ok(a)) if e.trim() == a.to_string().trim() => line.push_str(" - PASS"),
+                (Some(e), _) => line.push_str(&format!(
+                    " - FAIL, expected: {}",
+                    e.replace('\n', "\n        ")
+                )),
+                (None, _) => {}
+            }
+            line
+        })
+        .collect()
+}
+
+/// File name an input is listed under in answers files.
+pub fn input_name(input: &Path) -> String {
+    input
+        .file_name()
+        .map_or(String::new(), |n| n.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod watch_test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{Answer, Part};
+
+    fn report(part: Part, answer: i64) -> PartReport {
+        PartReport {
+            day: "day1".to_string(),
+            part,
+            answer: Ok(Answer::Int(answer)),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn describe_changes_test() {
+        let previous = vec![report(Part::One, 10), report(Part::Two, 20)];
+        let current = vec![report(Part::One, 10), report(Part::Two, 21)];
+        let answers = Answers::parse("[input.txt]\npart1: 10\npart2: 22").unwrap();
+
+        assert_eq!(
+            describe_changes(None, &current, None, "input.txt"),
+            vec!["Part 1: 10", "Part 2: 21"]
+        );
+        assert_eq!(
+            describe_changes(Some(&previous), &current, Some(&answers), "input.txt"),
+            vec![
+                "Part 1: 10 (unchanged) - PASS",
+                "Part 2: 21 (was: 20) - FAIL, expected: 22"
+            ]
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use std::path::Path;
+
+use adv_of_code_2022::verify::{self, Status};
+
+// one #[test] per inputs/dayN with a test_input.txt, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+
+fn check_examples(aoc_day: &str) {
+    let inputs_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+    let verifications = verify::verify_examples(&inputs_dir, aoc_day);
+
+    let failures: Vec<String> = verifications
+        .iter()
+        .filter(|v| v.status != Status::Pass)
+        .map(|v| format!("{} part {}: {:?}", v.input, v.part, v.status))
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "{} examples failed:\n{}",
+        aoc_day,
+        failures.join("\n")
+    );
+}